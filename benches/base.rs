@@ -20,7 +20,7 @@ fn main() {
     divan::main();
 }
 
-use risc0_verifier::{Digestible as _, VerifierContext};
+use risc0_verifier::{Digestible as _, Journal, Proof, VerifierContext, Vk};
 
 use utils::*;
 
@@ -125,3 +125,74 @@ pub mod succinct {
         )
     }
 }
+
+pub mod context {
+    use super::*;
+
+    // Building a context is a small fraction of a verification: compare the verification with a
+    // fresh context per call and with a reused one to measure what caching it saves.
+    #[divan::bench]
+    fn build_1_1() -> VerifierContext<
+        risc0_verifier::circuit::v1_1::CircuitImpl,
+        risc0_verifier::circuit::v1_1::recursive::CircuitImpl,
+    > {
+        VerifierContext::v1_1()
+    }
+
+    #[divan::bench]
+    fn build_1_2() -> VerifierContext<
+        risc0_verifier::circuit::v1_2::CircuitImpl,
+        risc0_verifier::circuit::v1_2::recursive::CircuitImpl,
+    > {
+        VerifierContext::v1_2()
+    }
+
+    fn succinct_1_2_0_16() -> (Proof, Case) {
+        let case: Case =
+            read_json("resources/cases/prover_1.2.0/vm_1.2.0/succinct_16.json").unwrap();
+        (read_bin(&case.receipt_path).unwrap(), case)
+    }
+
+    #[divan::bench]
+    fn verify_with_a_fresh_context(bencher: divan::Bencher) {
+        let (proof, case) = succinct_1_2_0_16();
+
+        bencher.bench_local(|| {
+            compute(
+                &VerifierContext::v1_2(),
+                divan::black_box(&proof),
+                divan::black_box(Vk(case.vk.0)),
+                divan::black_box(case.journal.digest()),
+            )
+        })
+    }
+
+    #[divan::bench]
+    fn verify_with_a_reused_context(bencher: divan::Bencher) {
+        let (proof, case) = succinct_1_2_0_16();
+        let ctx = VerifierContext::v1_2();
+
+        bencher.bench_local(|| {
+            compute(
+                divan::black_box(&ctx),
+                divan::black_box(&proof),
+                divan::black_box(Vk(case.vk.0)),
+                divan::black_box(case.journal.digest()),
+            )
+        })
+    }
+}
+
+pub mod journal {
+    use super::*;
+
+    // Compare with the `sha-soft` feature to measure the hardware acceleration of SHA-256.
+    #[divan::bench(args = [1 << 10, 1 << 16, 1 << 20])]
+    fn digest(bencher: divan::Bencher, len: usize) {
+        let journal = Journal::new(vec![0xa5; len]);
+
+        bencher
+            .counter(divan::counter::BytesCount::new(len))
+            .bench(|| divan::black_box(&journal).digest())
+    }
+}
@@ -0,0 +1,36 @@
+// Copyright 2024, Horizen Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Instantiates the whole verification path of `risc0-verifier` without the standard library:
+//! `cargo build --target thumbv7em-none-eabi` fails if any `std` only API creeps into it.
+
+#![no_std]
+
+use risc0_verifier::{Journal, Proof, VerificationError, VerifierContext, VerifierVersion, Vk};
+
+/// Verify a proof generated by the risc0 `1.2` prover.
+pub fn verify_v1_2(vk: Vk, proof: Proof, journal: Journal) -> Result<(), VerificationError> {
+    risc0_verifier::verify(&VerifierContext::v1_2(), vk, proof, journal)
+}
+
+/// Verify a proof with the verifier version selected by its verifier parameters.
+pub fn verify_auto(
+    proof: &Proof,
+    vk: Vk,
+    journal: &Journal,
+) -> Result<VerifierVersion, risc0_verifier::Error> {
+    use risc0_verifier::Digestible as _;
+    proof.verify_auto(vk, journal.digest())
+}
@@ -0,0 +1,183 @@
+// Copyright Copyright 2024, Horizen Labs, Inc.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! C ABI for the integrators that cannot link Rust code directly (e.g. through cgo).
+//!
+//! [risc0_verify] verifies a `bincode` encoded [Proof] (see [`Proof::to_bincode`]) with the risc0
+//! `1.2` [VerifierContext] and returns one of the following codes:
+//!
+//! | Code | Constant                        | Meaning                                        |
+//! |-----:|---------------------------------|------------------------------------------------|
+//! |    0 | [RISC0_OK]                      | The proof is valid                             |
+//! |   -1 | [RISC0_ERR_NULL_POINTER]        | A required pointer is null                     |
+//! |   -2 | [RISC0_ERR_INVALID_PROOF]       | The proof bytes are not a valid encoded proof  |
+//! |   -3 | [RISC0_ERR_VERIFICATION_FAILED] | The proof doesn't verify against the inputs    |
+//!
+//! On failure [risc0_last_error_message] describes the error. All the buffers are borrowed:
+//! the library never takes the ownership of the caller memory, nor it retains any pointer.
+
+use std::{cell::RefCell, ffi::CString, os::raw::c_char, string::ToString};
+
+use crate::{Digest, Digestible, Journal, Proof, VerifierContext};
+
+/// The proof is valid.
+pub const RISC0_OK: i32 = 0;
+/// A required pointer is null.
+pub const RISC0_ERR_NULL_POINTER: i32 = -1;
+/// The proof bytes are not a valid `bincode` encoded proof.
+pub const RISC0_ERR_INVALID_PROOF: i32 = -2;
+/// The proof doesn't verify against the given image id and journal.
+pub const RISC0_ERR_VERIFICATION_FAILED: i32 = -3;
+
+std::thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+fn set_last_error(code: i32, message: impl ToString) -> i32 {
+    // The messages never contain a nul byte, but don't panic across the FFI boundary anyway.
+    let message = CString::new(message.to_string().replace('\0', " ")).ok();
+    LAST_ERROR.with(|last| *last.borrow_mut() = message);
+    code
+}
+
+/// Verify the `bincode` encoded proof in `proof_ptr[..proof_len]` against the 32 bytes image id
+/// at `image_id_ptr` and the raw journal in `journal_ptr[..journal_len]`.
+///
+/// Returns [RISC0_OK] if the proof is valid, otherwise one of the error codes in the
+/// [module](self) documentation.
+///
+/// # Safety
+///
+/// `proof_ptr` must be valid for `proof_len` bytes reads, `image_id_ptr` for 32 bytes reads and
+/// `journal_ptr` for `journal_len` bytes reads (it may be null if `journal_len` is zero). The
+/// buffers must not be mutated during the call.
+#[no_mangle]
+pub unsafe extern "C" fn risc0_verify(
+    proof_ptr: *const u8,
+    proof_len: usize,
+    image_id_ptr: *const u8,
+    journal_ptr: *const u8,
+    journal_len: usize,
+) -> i32 {
+    if proof_ptr.is_null() || image_id_ptr.is_null() || (journal_ptr.is_null() && journal_len > 0) {
+        return set_last_error(RISC0_ERR_NULL_POINTER, "null pointer argument");
+    }
+    let proof_bytes = std::slice::from_raw_parts(proof_ptr, proof_len);
+    let image_id = std::slice::from_raw_parts(image_id_ptr, core::mem::size_of::<Digest>());
+    let journal = match journal_len {
+        0 => &[][..],
+        len => std::slice::from_raw_parts(journal_ptr, len),
+    };
+
+    let proof = match Proof::from_bincode(proof_bytes) {
+        Ok(proof) => proof,
+        Err(e) => return set_last_error(RISC0_ERR_INVALID_PROOF, e),
+    };
+    let image_id = Digest::try_from(image_id).expect("the slice has the digest length");
+    let journal = Journal::new(journal.to_vec());
+    match proof.verify(&VerifierContext::v1_2(), image_id, journal.digest()) {
+        Ok(()) => {
+            LAST_ERROR.with(|last| *last.borrow_mut() = None);
+            RISC0_OK
+        }
+        Err(e) => set_last_error(RISC0_ERR_VERIFICATION_FAILED, e),
+    }
+}
+
+/// The nul terminated message of the last error returned by [risc0_verify] on the calling
+/// thread, or null if the last call succeeded.
+///
+/// The string is owned by the library and it's valid till the next [risc0_verify] call on the
+/// same thread: the caller must copy it to keep it and must never free it.
+#[no_mangle]
+pub extern "C" fn risc0_last_error_message() -> *const c_char {
+    LAST_ERROR.with(|last| {
+        last.borrow()
+            .as_ref()
+            .map_or(core::ptr::null(), |message| message.as_ptr())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::succinct_case;
+    use std::{ffi::CStr, string::String};
+
+    fn verify(proof: &[u8], image_id: &[u8], journal: &[u8]) -> i32 {
+        unsafe {
+            risc0_verify(
+                proof.as_ptr(),
+                proof.len(),
+                image_id.as_ptr(),
+                journal.as_ptr(),
+                journal.len(),
+            )
+        }
+    }
+
+    fn last_error() -> Option<String> {
+        let message = risc0_last_error_message();
+        (!message.is_null()).then(|| {
+            unsafe { CStr::from_ptr(message) }
+                .to_string_lossy()
+                .into_owned()
+        })
+    }
+
+    #[test]
+    fn should_verify_a_valid_proof() {
+        let (bytes, case) = succinct_case();
+
+        assert_eq!(
+            verify(&bytes, case.vk.as_bytes(), &case.journal.bytes),
+            RISC0_OK
+        );
+        assert_eq!(last_error(), None);
+    }
+
+    #[test]
+    fn should_report_the_failures() {
+        let (bytes, case) = succinct_case();
+
+        assert_eq!(
+            verify(&bytes[1..], case.vk.as_bytes(), &case.journal.bytes),
+            RISC0_ERR_INVALID_PROOF
+        );
+        assert!(last_error().is_some());
+
+        assert_eq!(
+            verify(&bytes, case.vk.as_bytes(), &[]),
+            RISC0_ERR_VERIFICATION_FAILED
+        );
+        assert!(last_error()
+            .unwrap()
+            .starts_with("claim digest does not match"));
+
+        let code = unsafe {
+            risc0_verify(
+                core::ptr::null(),
+                0,
+                case.vk.as_bytes().as_ptr(),
+                core::ptr::null(),
+                0,
+            )
+        };
+        assert_eq!(code, RISC0_ERR_NULL_POINTER);
+        assert_eq!(last_error().as_deref(), Some("null pointer argument"));
+    }
+}
@@ -15,6 +15,12 @@
 // limitations under the License.
 //
 
+//! The risc0 circuits supported by the verifier, one module for each incompatible vm version,
+//! and the precomputed control IDs to verify their proofs.
+
+use alloc::string::String;
+use core::{fmt, str::FromStr};
+
 pub trait CircuitCoreDef:
     risc0_zkp::adapter::CircuitCoreDef<risc0_zkp::field::baby_bear::BabyBear> + 'static
 {
@@ -25,8 +31,235 @@ impl<T: risc0_zkp::adapter::CircuitCoreDef<risc0_zkp::field::baby_bear::BabyBear
 {
 }
 
+/// The hash functions that have precomputed control IDs.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum HashFn {
+    /// SHA-256, named `"sha-256"`.
+    Sha256,
+    /// Poseidon2, named `"poseidon2"`.
+    Poseidon2,
+    /// Blake2b, named `"blake2b"`.
+    Blake2b,
+}
+
+impl HashFn {
+    /// All the hash functions.
+    pub const ALL: [Self; 3] = [Self::Sha256, Self::Poseidon2, Self::Blake2b];
+
+    /// The name used by risc0 to identify the hash function, e.g. in the receipts and in the
+    /// [VerifierContext][crate::VerifierContext] suites.
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Self::Sha256 => "sha-256",
+            Self::Poseidon2 => "poseidon2",
+            Self::Blake2b => "blake2b",
+        }
+    }
+}
+
+impl fmt::Display for HashFn {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Error returned when parsing an unknown hash function name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownHashFnError(pub String);
+
+impl fmt::Display for UnknownHashFnError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "unknown hash function: {}", &self.0)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for UnknownHashFnError {}
+
+impl FromStr for HashFn {
+    type Err = UnknownHashFnError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|hash| hash.name() == s)
+            .ok_or_else(|| UnknownHashFnError(s.into()))
+    }
+}
+
+/// Error returned when a control ID cannot be found in the precomputed tables.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ControlIdError {
+    /// The hash function name is unknown.
+    UnknownHash(String),
+    /// The po2 is outside the `min..=max` range of the precomputed tables.
+    Po2OutOfRange {
+        /// The requested po2.
+        po2: usize,
+        /// The minimum supported po2.
+        min: usize,
+        /// The maximum supported po2.
+        max: usize,
+    },
+}
+
+impl fmt::Display for ControlIdError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::UnknownHash(name) => write!(f, "unknown hash function: {name}"),
+            Self::Po2OutOfRange { po2, min, max } => {
+                write!(f, "po2 {po2} is out of the supported range {min}..={max}")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ControlIdError {}
+
+impl From<UnknownHashFnError> for ControlIdError {
+    fn from(e: UnknownHashFnError) -> Self {
+        Self::UnknownHash(e.0)
+    }
+}
+
 pub mod v1_0;
 
 pub mod v1_1;
 
 pub mod v1_2;
+
+#[cfg(feature = "v1_3")]
+pub mod v1_3;
+
+/// A segment circuit with the seal layout shared by all the supported versions, used to read the
+/// seal headers without a [VerifierContext](crate::VerifierContext), e.g. to validate a proof
+/// structure: the versions must have the same outputs size, checked at compile time.
+pub(crate) type SharedSegmentLayout = v1_2::CircuitImpl;
+
+/// A recursion circuit with the seal layout shared by all the supported versions: refer to
+/// [SharedSegmentLayout].
+pub(crate) type SharedRecursionLayout = v1_2::recursive::CircuitImpl;
+
+const _: () = {
+    use risc0_zkp::adapter::CircuitInfo as _;
+
+    const SEGMENT: usize = SharedSegmentLayout::OUTPUT_SIZE;
+    const RECURSION: usize = SharedRecursionLayout::OUTPUT_SIZE;
+    assert!(v1_0::CircuitImpl::OUTPUT_SIZE == SEGMENT);
+    assert!(v1_1::CircuitImpl::OUTPUT_SIZE == SEGMENT);
+    assert!(v1_0::recursive::CircuitImpl::OUTPUT_SIZE == RECURSION);
+    assert!(v1_1::recursive::CircuitImpl::OUTPUT_SIZE == RECURSION);
+    #[cfg(feature = "v1_3")]
+    assert!(v1_3::CircuitImpl::OUTPUT_SIZE == SEGMENT);
+    #[cfg(feature = "v1_3")]
+    assert!(v1_3::recursive::CircuitImpl::OUTPUT_SIZE == RECURSION);
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::ToString;
+    use risc0_zkp::{MAX_CYCLES_PO2, MIN_CYCLES_PO2};
+    use rstest::rstest;
+
+    #[rstest]
+    #[case::sha256(HashFn::Sha256, "sha-256")]
+    #[case::poseidon2(HashFn::Poseidon2, "poseidon2")]
+    #[case::blake2b(HashFn::Blake2b, "blake2b")]
+    fn hash_fn_name_round_trip(#[case] hash: HashFn, #[case] name: &str) {
+        assert_eq!(hash.to_string(), name);
+        assert_eq!(name.parse(), Ok(hash));
+    }
+
+    #[rstest]
+    #[case::no_dash("sha256")]
+    #[case::upper_case("SHA-256")]
+    #[case::empty("")]
+    fn reject_unknown_hash_fn_name(#[case] name: &str) {
+        assert_eq!(
+            name.parse::<HashFn>(),
+            Err(UnknownHashFnError(name.to_string()))
+        );
+    }
+
+    #[rstest]
+    fn string_control_id_lookup_matches_the_enum_one(
+        #[values(HashFn::Sha256, HashFn::Poseidon2, HashFn::Blake2b)] hash: HashFn,
+        #[values(13, 16, 21, 24)] po2: usize,
+    ) {
+        assert!(v1_2::control_id_by_fn(hash, po2).is_some());
+        assert_eq!(
+            v1_1::control_id(hash.name(), po2),
+            v1_1::control_id_by_fn(hash, po2)
+        );
+        assert_eq!(
+            v1_2::control_id(hash.name(), po2),
+            v1_2::control_id_by_fn(hash, po2)
+        );
+        assert_eq!(v1_2::control_id("sha256", po2), None);
+    }
+
+    #[rstest]
+    fn control_ids_yield_the_whole_table(
+        #[values(HashFn::Sha256, HashFn::Poseidon2, HashFn::Blake2b)] hash: HashFn,
+    ) {
+        let ids = v1_2::control_ids(hash).collect::<std::vec::Vec<_>>();
+
+        assert_eq!(ids.len(), MAX_CYCLES_PO2 - MIN_CYCLES_PO2 + 1);
+        assert_eq!(ids.first().unwrap().0, MIN_CYCLES_PO2);
+        assert_eq!(ids.last().unwrap().0, MAX_CYCLES_PO2);
+        assert!(ids
+            .iter()
+            .all(|&(po2, id)| v1_2::control_id_by_fn(hash, po2) == Some(id)));
+        assert!(v1_1::control_ids(hash).eq(ids.iter().copied()));
+    }
+
+    #[rstest]
+    fn identify_the_precomputed_control_ids(
+        #[values(HashFn::Sha256, HashFn::Poseidon2, HashFn::Blake2b)] hash: HashFn,
+        #[values(13, 16, 21, 24)] po2: usize,
+    ) {
+        let id = v1_2::control_id_by_fn(hash, po2).unwrap();
+
+        assert_eq!(v1_2::identify_control_id(id), Some((hash, po2)));
+        assert_eq!(
+            v1_1::identify_control_id(v1_1::control_id_by_fn(hash, po2).unwrap()),
+            Some((hash, po2))
+        );
+    }
+
+    #[test]
+    fn unknown_control_ids_are_not_identified() {
+        use risc0_zkp::core::digest::Digest;
+
+        assert_eq!(v1_2::identify_control_id(Digest::ZERO), None);
+        assert_eq!(
+            v1_2::identify_control_id(v1_2::recursive::control_id::ALLOWED_CONTROL_ROOT),
+            None
+        );
+    }
+
+    #[test]
+    fn try_control_id_explains_the_failure() {
+        assert_eq!(
+            v1_2::try_control_id("sha-256", 16).ok(),
+            v1_2::control_id_by_fn(HashFn::Sha256, 16)
+        );
+        assert_eq!(
+            v1_2::try_control_id("sha256", 16),
+            Err(ControlIdError::UnknownHash("sha256".to_string()))
+        );
+        for po2 in [12, 25] {
+            assert_eq!(
+                v1_1::try_control_id("poseidon2", po2),
+                Err(ControlIdError::Po2OutOfRange {
+                    po2,
+                    min: 13,
+                    max: 24
+                })
+            );
+            assert_eq!(v1_1::control_id("poseidon2", po2), None);
+        }
+    }
+}
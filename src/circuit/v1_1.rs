@@ -24,6 +24,8 @@ use risc0_zkp::{
 };
 use risc0_zkp::{MAX_CYCLES_PO2, MIN_CYCLES_PO2};
 
+use super::{ControlIdError, HashFn};
+
 pub mod control_id;
 mod poly_ext;
 mod taps;
@@ -61,18 +63,56 @@ impl CircuitCoreDef<BabyBear> for CircuitImpl {}
 /// the precomputed table. If the hash function is not precomputed, or the po2 is out of range,
 /// this function will return `None`.
 ///
-/// Supported values for hash_name are "sha-256", "poseidon2", and "blake2b".
+/// Supported values for hash_name are "sha-256", "poseidon2", and "blake2b": prefer
+/// [control_id_by_fn] that cannot fail on a misspelled name, or [try_control_id] to know why the
+/// lookup failed.
 pub fn control_id(hash_name: &str, po2: usize) -> Option<Digest> {
+    try_control_id(hash_name, po2).ok()
+}
+
+/// Fetch a control ID like [control_id], but returning a [ControlIdError] that tells whether the
+/// hash function name is unknown or the po2 is out of range.
+pub fn try_control_id(hash_name: &str, po2: usize) -> Result<Digest, ControlIdError> {
+    control_id_by_fn(hash_name.parse()?, po2).ok_or(ControlIdError::Po2OutOfRange {
+        po2,
+        min: MIN_CYCLES_PO2,
+        max: MAX_CYCLES_PO2,
+    })
+}
+
+/// Fetch a control ID with the given hash function and cycle limit as a power of two (po2) from
+/// the precomputed table. If the po2 is out of range, this function will return `None`.
+pub fn control_id_by_fn(hash: HashFn, po2: usize) -> Option<Digest> {
     if !(MIN_CYCLES_PO2..=MAX_CYCLES_PO2).contains(&po2) {
         return None;
     }
-    let idx = po2 - MIN_CYCLES_PO2;
+    Some(control_ids_table(hash)[po2 - MIN_CYCLES_PO2])
+}
+
+/// Iterate over all the precomputed control IDs for the given hash function, paired with their
+/// cycle limit as a power of two (po2), from `MIN_CYCLES_PO2` to `MAX_CYCLES_PO2`.
+pub fn control_ids(hash: HashFn) -> impl Iterator<Item = (usize, Digest)> {
+    (MIN_CYCLES_PO2..).zip(control_ids_table(hash).iter().copied())
+}
+
+/// Find the hash function and the cycle limit as a power of two (po2) of a precomputed control
+/// ID, the inverse of [control_id_by_fn], with a linear scan of the tables. Returns `None` for a
+/// custom or unknown control ID, like the recursion program ones of the succinct receipts, that
+/// are listed in [recursive::control_id].
+pub fn identify_control_id(id: Digest) -> Option<(HashFn, usize)> {
+    HashFn::ALL.into_iter().find_map(|hash| {
+        control_ids(hash)
+            .find(|&(_, candidate)| candidate == id)
+            .map(|(po2, _)| (hash, po2))
+    })
+}
+
+fn control_ids_table(hash: HashFn) -> &'static control_id::ControlIds {
     use control_id::*;
-    match hash_name {
-        "sha-256" => Some(SHA256_CONTROL_IDS[idx]),
-        "poseidon2" => Some(POSEIDON2_CONTROL_IDS[idx]),
-        "blake2b" => Some(BLAKE2B_CONTROL_IDS[idx]),
-        _ => None,
+    match hash {
+        HashFn::Sha256 => &SHA256_CONTROL_IDS,
+        HashFn::Poseidon2 => &POSEIDON2_CONTROL_IDS,
+        HashFn::Blake2b => &BLAKE2B_CONTROL_IDS,
     }
 }
 
@@ -0,0 +1,182 @@
+// Copyright Copyright 2024, Horizen Labs, Inc.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Scaffold for the risc0 vm `1.3.x` circuit, available with the `v1_3` feature.
+//!
+//! The `1.3` constants are not published yet: the circuit evaluation delegates to the
+//! [`1.2`](super::v1_2) one and the control ID tables are empty, so no proof can verify against
+//! this circuit till they are filled in.
+
+use risc0_core::field::baby_bear::{BabyBear, BabyBearElem, BabyBearExtElem};
+use risc0_zkp::core::digest::Digest;
+
+use risc0_zkp::{
+    adapter::{CircuitCoreDef, CircuitInfo, MixState, PolyExt, ProtocolInfo, TapsProvider},
+    taps::TapSet,
+};
+use risc0_zkp::{MAX_CYCLES_PO2, MIN_CYCLES_PO2};
+
+use super::{v1_2, ControlIdError, HashFn};
+
+pub mod control_id;
+
+pub const CIRCUIT: CircuitImpl = CircuitImpl::new();
+
+pub struct CircuitImpl;
+
+impl CircuitImpl {
+    const fn new() -> Self {
+        CircuitImpl
+    }
+}
+
+impl TapsProvider for CircuitImpl {
+    fn get_taps(&self) -> &'static TapSet<'static> {
+        v1_2::CIRCUIT.get_taps()
+    }
+}
+
+impl PolyExt<BabyBear> for CircuitImpl {
+    fn poly_ext(
+        &self,
+        mix: &BabyBearExtElem,
+        u: &[BabyBearExtElem],
+        args: &[&[BabyBearElem]],
+    ) -> MixState<BabyBearExtElem> {
+        v1_2::CIRCUIT.poly_ext(mix, u, args)
+    }
+}
+
+impl CircuitInfo for CircuitImpl {
+    const CIRCUIT_INFO: ProtocolInfo = v1_2::CircuitImpl::CIRCUIT_INFO;
+
+    const OUTPUT_SIZE: usize = v1_2::CircuitImpl::OUTPUT_SIZE;
+
+    const MIX_SIZE: usize = v1_2::CircuitImpl::MIX_SIZE;
+}
+
+impl CircuitCoreDef<BabyBear> for CircuitImpl {}
+
+/// Fetch a control ID with the given hash, by name, and cycle limit as a power of two (po2) from
+/// the precomputed table. If the hash function is not precomputed, or the po2 is out of range,
+/// this function will return `None`.
+///
+/// Supported values for hash_name are "sha-256", "poseidon2", and "blake2b": prefer
+/// [control_id_by_fn] that cannot fail on a misspelled name, or [try_control_id] to know why the
+/// lookup failed.
+pub fn control_id(hash_name: &str, po2: usize) -> Option<Digest> {
+    try_control_id(hash_name, po2).ok()
+}
+
+/// Fetch a control ID like [control_id], but returning a [ControlIdError] that tells whether the
+/// hash function name is unknown or the po2 is out of range.
+pub fn try_control_id(hash_name: &str, po2: usize) -> Result<Digest, ControlIdError> {
+    control_id_by_fn(hash_name.parse()?, po2).ok_or(ControlIdError::Po2OutOfRange {
+        po2,
+        min: MIN_CYCLES_PO2,
+        max: MAX_CYCLES_PO2,
+    })
+}
+
+/// Fetch a control ID with the given hash function and cycle limit as a power of two (po2) from
+/// the precomputed table. If the po2 is out of range, or the table is still empty, this function
+/// will return `None`.
+pub fn control_id_by_fn(hash: HashFn, po2: usize) -> Option<Digest> {
+    if !(MIN_CYCLES_PO2..=MAX_CYCLES_PO2).contains(&po2) {
+        return None;
+    }
+    control_ids_table(hash).get(po2 - MIN_CYCLES_PO2).copied()
+}
+
+/// Iterate over all the precomputed control IDs for the given hash function, paired with their
+/// cycle limit as a power of two (po2), from `MIN_CYCLES_PO2` to `MAX_CYCLES_PO2`.
+pub fn control_ids(hash: HashFn) -> impl Iterator<Item = (usize, Digest)> {
+    (MIN_CYCLES_PO2..).zip(control_ids_table(hash).iter().copied())
+}
+
+/// Find the hash function and the cycle limit as a power of two (po2) of a precomputed control
+/// ID, the inverse of [control_id_by_fn], with a linear scan of the tables. Returns `None` for a
+/// custom or unknown control ID, like the recursion program ones of the succinct receipts, that
+/// are listed in [recursive::control_id].
+pub fn identify_control_id(id: Digest) -> Option<(HashFn, usize)> {
+    HashFn::ALL.into_iter().find_map(|hash| {
+        control_ids(hash)
+            .find(|&(_, candidate)| candidate == id)
+            .map(|(po2, _)| (hash, po2))
+    })
+}
+
+fn control_ids_table(hash: HashFn) -> control_id::ControlIds {
+    use control_id::*;
+    match hash {
+        HashFn::Sha256 => SHA256_CONTROL_IDS,
+        HashFn::Poseidon2 => POSEIDON2_CONTROL_IDS,
+        HashFn::Blake2b => BLAKE2B_CONTROL_IDS,
+    }
+}
+
+pub mod recursive {
+    use risc0_zkp::{
+        adapter::{CircuitCoreDef, CircuitInfo, MixState, PolyExt, ProtocolInfo, TapsProvider},
+        field::baby_bear::{BabyBear, BabyBearElem, BabyBearExtElem},
+        taps::TapSet,
+    };
+
+    use crate::circuit::v1_2::recursive as v1_2;
+
+    #[allow(dead_code)]
+    pub mod control_id;
+
+    /// This struct implements traits that are defined by code generated by the
+    /// circuit definition.
+    pub struct CircuitImpl;
+
+    impl CircuitImpl {
+        const fn new() -> Self {
+            CircuitImpl
+        }
+    }
+
+    impl TapsProvider for CircuitImpl {
+        fn get_taps(&self) -> &'static TapSet<'static> {
+            v1_2::CIRCUIT.get_taps()
+        }
+    }
+
+    impl PolyExt<BabyBear> for CircuitImpl {
+        fn poly_ext(
+            &self,
+            mix: &BabyBearExtElem,
+            u: &[BabyBearExtElem],
+            args: &[&[BabyBearElem]],
+        ) -> MixState<BabyBearExtElem> {
+            v1_2::CIRCUIT.poly_ext(mix, u, args)
+        }
+    }
+
+    pub const CIRCUIT: CircuitImpl = CircuitImpl::new();
+
+    impl CircuitInfo for CircuitImpl {
+        const CIRCUIT_INFO: ProtocolInfo = v1_2::CircuitImpl::CIRCUIT_INFO;
+
+        const OUTPUT_SIZE: usize = v1_2::CircuitImpl::OUTPUT_SIZE;
+
+        const MIX_SIZE: usize = v1_2::CircuitImpl::MIX_SIZE;
+    }
+
+    impl CircuitCoreDef<BabyBear> for CircuitImpl {}
+}
@@ -0,0 +1,33 @@
+// Copyright Copyright 2024, Horizen Labs, Inc.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Placeholder: to be replaced with the control IDs published with risc0 `1.3`.
+
+use risc0_zkp::core::digest::Digest;
+
+/// The control IDs of a hash function, one per power-of-two from `MIN_CYCLES_PO2`: empty till
+/// they are published, so no segment is accepted.
+pub type ControlIds = &'static [Digest];
+
+/// Control IDs for each power-of-two of the rv32im circuit using SHA-256.
+pub const SHA256_CONTROL_IDS: ControlIds = &[];
+
+/// Control IDs for each power-of-two of the rv32im circuit using Poseidon2.
+pub const POSEIDON2_CONTROL_IDS: ControlIds = &[];
+
+/// Control IDs for each power-of-two of the rv32im circuit using Blake2b.
+pub const BLAKE2B_CONTROL_IDS: ControlIds = &[];
@@ -0,0 +1,29 @@
+// Copyright Copyright 2024, Horizen Labs, Inc.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Placeholder: to be replaced with the control IDs published with risc0 `1.3`.
+
+use risc0_zkp::core::digest::Digest;
+
+/// Control IDs allowed in the default set of recursion programs.
+pub const ALLOWED_CONTROL_IDS: &[Digest] = &[];
+
+/// Root of the Merkle tree constructed from [ALLOWED_CONTROL_IDS], using Poseidon2.
+pub const ALLOWED_CONTROL_ROOT: Digest = Digest::ZERO;
+
+/// Control ID for the identity recursion programs (ZKR), using Poseidon over the BN254 scalar field.
+pub const BN254_IDENTITY_CONTROL_ID: Digest = Digest::ZERO;
@@ -15,19 +15,37 @@
 // limitations under the License.
 //
 
-use alloc::{collections::BTreeMap, string::String};
+use alloc::{
+    collections::{BTreeMap, BTreeSet},
+    rc::Rc,
+    string::String,
+};
+use core::fmt;
+
+use risc0_binfmt::tagged_struct;
 use risc0_zkp::{
-    core::hash::{
-        blake2b::Blake2bCpuHashSuite, poseidon2::Poseidon2HashSuite, sha::Sha256HashSuite,
-        HashSuite,
+    adapter::ProtocolInfo,
+    core::{
+        digest::{digest, Digest},
+        hash::{
+            blake2b::Blake2bCpuHashSuite, poseidon2::Poseidon2HashSuite, sha::Sha256HashSuite,
+            HashSuite,
+        },
     },
     field::baby_bear::BabyBear,
+    verify::VerificationError,
+    MAX_CYCLES_PO2, MIN_CYCLES_PO2,
 };
 
+#[cfg(feature = "groth16")]
+use crate::receipt::groth16::Groth16ReceiptVerifierParameters;
 use crate::{
-    circuit::{self, CircuitCoreDef},
-    receipt::succinct::SuccinctReceiptVerifierParameters,
+    circuit::{self, CircuitCoreDef, HashFn},
+    error::Error,
+    observer::{VerifyCheck, VerifyObserver},
+    receipt::{succinct::SuccinctReceiptVerifierParameters, DEFAULT_MAX_PO2},
     segment::SegmentReceiptVerifierParameters,
+    sha::{self, Digestible},
 };
 
 /// Context available to the verification process. The context contains
@@ -42,9 +60,14 @@ use crate::{
 /// So, `VerifierContext` define a new constructor for each risc0 minor version
 /// in order to have the right context for any risc0 incompatible vm version.
 ///
+/// Building a context is cheap, about a microsecond against the milliseconds of a verification
+/// (see the `context` benches): the circuit tables are compile time constants and only the hash
+/// suites and the verifier parameters are allocated. The context holds the hash suites behind an `Rc`, so it can't be
+/// shared in a `static`: build one per thread and reuse it across the verifications.
 #[non_exhaustive]
 pub struct VerifierContext<SC: CircuitCoreDef, RC: CircuitCoreDef> {
-    /// A registry of hash functions to be used by the verification process.
+    /// A registry of hash functions to be used by the verification process. They only verify the
+    /// seals, not the [journal digest](crate::Journal#impl-Digestible-for-Journal).
     pub suites: BTreeMap<String, HashSuite<BabyBear>>,
 
     /// Parameters for verification of [SegmentReceipt].
@@ -53,38 +76,246 @@ pub struct VerifierContext<SC: CircuitCoreDef, RC: CircuitCoreDef> {
     /// Parameters for verification of [SuccinctReceipt].
     pub succinct_verifier_parameters: Option<SuccinctReceiptVerifierParameters>,
 
+    /// Parameters for verification of [Groth16Receipt].
+    #[cfg(feature = "groth16")]
+    pub groth16_verifier_parameters: Option<Groth16ReceiptVerifierParameters>,
+
     pub circuit: &'static SC,
 
     pub recursive_circuit: &'static RC,
+
+    /// Maximum segment size, as a power of two (po2), accepted by the verification. The built-in
+    /// contexts can change it with `with_max_po2()`, that also updates the segment control IDs.
+    pub max_po2: usize,
+
+    /// Optional observer notified about the verification progress.
+    pub observer: Option<Rc<dyn VerifyObserver>>,
+
+    /// Reject the receipts whose [ReceiptClaim](crate::ReceiptClaim) is pruned, even if the seal
+    /// attests to its digest. Only the claim itself must be a value: its fields can be pruned.
+    pub strict_claims: bool,
+
+    /// Maximum journal length, in bytes, accepted by
+    /// [`Proof::verify_with_journal`](crate::Proof::verify_with_journal) before hashing it:
+    /// `None`, the default, doesn't bound it.
+    pub max_journal_len: Option<usize>,
+
+    /// Optional policy on top of the verifier parameters: the segment and the succinct receipts
+    /// are accepted only if their seal is proven with one of these control IDs. `None`, the
+    /// default, accepts all the control IDs allowed by the parameters.
+    pub allowed_control_ids: Option<BTreeSet<Digest>>,
+
+    /// Maximum number of segments of a [CompositeReceipt](crate::CompositeReceipt) accepted by
+    /// the verification, checked before verifying the seals: `None`, the default, doesn't bound it.
+    pub max_segments: Option<usize>,
+
+    /// Maximum number of assumption receipts of a [CompositeReceipt](crate::CompositeReceipt)
+    /// accepted by the verification, checked before verifying the seals: `None`, the default,
+    /// doesn't bound it. The composite assumption receipts are bound by the same limits.
+    pub max_assumptions: Option<usize>,
+}
+
+impl<SC: CircuitCoreDef, RC: CircuitCoreDef> fmt::Display for VerifierContext<SC, RC> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// The risc0 vm versions that have a built-in [VerifierContext].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum VerifierVersion {
+    /// Risc0 vm `1.0.x`: verified with [`VerifierContext::v1_0()`].
+    V1_0,
+    /// Risc0 vm `1.1.x`: verified with [`VerifierContext::v1_1()`].
+    V1_1,
+    /// Risc0 vm `1.2.x`: verified with [`VerifierContext::v1_2()`].
+    V1_2,
+}
+
+impl VerifierVersion {
+    /// All the known versions, from the oldest to the newest.
+    pub const ALL: [Self; 3] = [Self::V1_0, Self::V1_1, Self::V1_2];
+
+    /// Return the version whose built-in [VerifierContext] has verifier parameters with the given
+    /// digest (as returned by [`InnerReceipt::verifier_parameters()`][crate::InnerReceipt::verifier_parameters]),
+    /// or `None` if the digest is unknown.
+    pub fn for_parameters(params: Digest) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|version| version.verifier_parameters().contains(&params))
+    }
+
+    /// Return the version whose built-in [VerifierContext] enforces the given control root (see
+    /// [`VerifierContext::control_root()`]), or `None` if the control root is unknown.
+    pub fn for_control_root(control_root: Digest) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|version| version.control_root() == control_root)
+    }
+
+    /// Human readable name of this version, like `risc0-v1.2`.
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Self::V1_0 => "risc0-v1.0",
+            Self::V1_1 => "risc0-v1.1",
+            Self::V1_2 => "risc0-v1.2",
+        }
+    }
+
+    fn control_root(&self) -> Digest {
+        match self {
+            Self::V1_0 => circuit::v1_0::recursive::control_id::ALLOWED_CONTROL_ROOT,
+            Self::V1_1 => circuit::v1_1::recursive::control_id::ALLOWED_CONTROL_ROOT,
+            Self::V1_2 => circuit::v1_2::recursive::control_id::ALLOWED_CONTROL_ROOT,
+        }
+    }
+
+    /// Digests of the verifier parameters that the receipts generated by this version declare:
+    /// the composite receipts carry the digest of all the parameters, while the succinct and
+    /// groth16 receipts carry just their own ones.
+    fn verifier_parameters(&self) -> [Digest; 3] {
+        let (segment, succinct) = match self {
+            Self::V1_0 => (
+                SegmentReceiptVerifierParameters::v1_0().digest(),
+                SuccinctReceiptVerifierParameters::v1_0().digest(),
+            ),
+            Self::V1_1 => (
+                SegmentReceiptVerifierParameters::v1_1().digest(),
+                SuccinctReceiptVerifierParameters::v1_1().digest(),
+            ),
+            Self::V1_2 => (
+                SegmentReceiptVerifierParameters::v1_2().digest(),
+                SuccinctReceiptVerifierParameters::v1_2().digest(),
+            ),
+        };
+        let groth16 = self.groth16_verifier_parameters();
+        let composite = tagged_struct::<sha::Impl>(
+            "risc0.CompositeReceiptVerifierParameters",
+            &[segment, succinct, groth16],
+            &[],
+        );
+        [composite, succinct, groth16]
+    }
+
+    /// Digest of the groth16 verifier parameters: hardcoded because they are available only
+    /// with the `groth16` feature.
+    pub(crate) fn groth16_verifier_parameters(&self) -> Digest {
+        match self {
+            Self::V1_0 => {
+                digest!("310fe598e8e3e92fa805bc272d7f587898bb8b68c4d5d7938db884abaa76e15c")
+            }
+            Self::V1_1 => {
+                digest!("50bd1769093e74abda3711c315d84d78e3e282173f6304a33272d92abb590ef5")
+            }
+            Self::V1_2 => {
+                digest!("c101b42bcacd62e35222b1207223250814d05dd41d41f8cadc1f16f86707ae15")
+            }
+        }
+    }
+}
+
+impl fmt::Display for VerifierVersion {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
+    }
 }
 
 impl VerifierContext<circuit::v1_0::CircuitImpl, circuit::v1_0::recursive::CircuitImpl> {
     /// Create an empty [VerifierContext] for any risc0 proof generate for any `1.0.x` vm version.
     pub fn v1_0() -> Self {
-        Self::empty(&circuit::v1_0::CIRCUIT, &circuit::v1_0::recursive::CIRCUIT)
+        let ctx = Self::empty(&circuit::v1_0::CIRCUIT, &circuit::v1_0::recursive::CIRCUIT)
             .with_suites(Self::default_hash_suites())
             .with_segment_verifier_parameters(SegmentReceiptVerifierParameters::v1_0())
-            .with_succinct_verifier_parameters(SuccinctReceiptVerifierParameters::v1_0())
+            .with_succinct_verifier_parameters(SuccinctReceiptVerifierParameters::v1_0());
+        #[cfg(feature = "groth16")]
+        let ctx = ctx.with_groth16_verifier_parameters(Groth16ReceiptVerifierParameters::v1_0());
+        ctx
+    }
+
+    /// Return [VerifierContext] that accepts segments up to the given size, as a power of two
+    /// (po2), instead of [DEFAULT_MAX_PO2]. The `1.0.x` control IDs are the same for every po2,
+    /// so just the segment size is checked.
+    ///
+    /// Fails with [Error::Po2OutOfRange] if `po2` is outside the
+    /// `MIN_CYCLES_PO2..=MAX_CYCLES_PO2` range.
+    pub fn with_max_po2(self, po2: usize) -> Result<Self, Error> {
+        self.with_checked_max_po2(po2, None)
     }
 }
 
 impl VerifierContext<circuit::v1_1::CircuitImpl, circuit::v1_1::recursive::CircuitImpl> {
     /// Create an empty [VerifierContext] for any risc0 proof generate for any `1.1.x` vm version.
     pub fn v1_1() -> Self {
-        Self::empty(&circuit::v1_1::CIRCUIT, &circuit::v1_1::recursive::CIRCUIT)
+        let ctx = Self::empty(&circuit::v1_1::CIRCUIT, &circuit::v1_1::recursive::CIRCUIT)
             .with_suites(Self::default_hash_suites())
             .with_segment_verifier_parameters(SegmentReceiptVerifierParameters::v1_1())
-            .with_succinct_verifier_parameters(SuccinctReceiptVerifierParameters::v1_1())
+            .with_succinct_verifier_parameters(SuccinctReceiptVerifierParameters::v1_1());
+        #[cfg(feature = "groth16")]
+        let ctx = ctx.with_groth16_verifier_parameters(Groth16ReceiptVerifierParameters::v1_1());
+        ctx
+    }
+
+    /// Return [VerifierContext] that accepts segments up to the given size, as a power of two
+    /// (po2), instead of [DEFAULT_MAX_PO2]. The segment verifier parameters are updated to accept
+    /// the control IDs up to the new limit.
+    ///
+    /// A po2 greater than [DEFAULT_MAX_PO2] degrades the security of 1 bit per po2, while a
+    /// smaller one rejects the proofs with bigger segments. Fails with [Error::Po2OutOfRange] if
+    /// `po2` is outside the `MIN_CYCLES_PO2..=MAX_CYCLES_PO2` range.
+    pub fn with_max_po2(self, po2: usize) -> Result<Self, Error> {
+        self.with_checked_max_po2(po2, Some(&circuit::v1_1::control_id_by_fn))
     }
 }
 
 impl VerifierContext<circuit::v1_2::CircuitImpl, circuit::v1_2::recursive::CircuitImpl> {
     /// Create an empty [VerifierContext] for any risc0 proof generate for any `1.2.x` vm version.
     pub fn v1_2() -> Self {
-        Self::empty(&circuit::v1_2::CIRCUIT, &circuit::v1_2::recursive::CIRCUIT)
+        let ctx = Self::empty(&circuit::v1_2::CIRCUIT, &circuit::v1_2::recursive::CIRCUIT)
             .with_suites(Self::default_hash_suites())
             .with_segment_verifier_parameters(SegmentReceiptVerifierParameters::v1_2())
-            .with_succinct_verifier_parameters(SuccinctReceiptVerifierParameters::v1_2())
+            .with_succinct_verifier_parameters(SuccinctReceiptVerifierParameters::v1_2());
+        #[cfg(feature = "groth16")]
+        let ctx = ctx.with_groth16_verifier_parameters(Groth16ReceiptVerifierParameters::v1_2());
+        ctx
+    }
+
+    /// Return [VerifierContext] that accepts segments up to the given size, as a power of two
+    /// (po2), instead of [DEFAULT_MAX_PO2]. The segment verifier parameters are updated to accept
+    /// the control IDs up to the new limit.
+    ///
+    /// A po2 greater than [DEFAULT_MAX_PO2] degrades the security of 1 bit per po2, while a
+    /// smaller one rejects the proofs with bigger segments. Fails with [Error::Po2OutOfRange] if
+    /// `po2` is outside the `MIN_CYCLES_PO2..=MAX_CYCLES_PO2` range.
+    pub fn with_max_po2(self, po2: usize) -> Result<Self, Error> {
+        self.with_checked_max_po2(po2, Some(&circuit::v1_2::control_id_by_fn))
+    }
+}
+
+#[cfg(feature = "v1_3")]
+impl VerifierContext<circuit::v1_3::CircuitImpl, circuit::v1_3::recursive::CircuitImpl> {
+    /// Create an empty [VerifierContext] for the risc0 `1.3.x` vm versions.
+    ///
+    /// This is a scaffold available with the `v1_3` feature: the `1.3` control IDs are still
+    /// placeholders, so no proof verifies with this context yet, and it has no [VerifierVersion].
+    pub fn v1_3() -> Self {
+        let ctx = Self::empty(&circuit::v1_3::CIRCUIT, &circuit::v1_3::recursive::CIRCUIT)
+            .with_suites(Self::default_hash_suites())
+            .with_segment_verifier_parameters(SegmentReceiptVerifierParameters::v1_3())
+            .with_succinct_verifier_parameters(SuccinctReceiptVerifierParameters::v1_3());
+        #[cfg(feature = "groth16")]
+        let ctx = ctx.with_groth16_verifier_parameters(Groth16ReceiptVerifierParameters::v1_3());
+        ctx
+    }
+
+    /// Return [VerifierContext] that accepts segments up to the given size, as a power of two
+    /// (po2), instead of [DEFAULT_MAX_PO2]. The segment verifier parameters are updated to accept
+    /// the control IDs up to the new limit.
+    ///
+    /// Fails with [Error::Po2OutOfRange] if `po2` is outside the
+    /// `MIN_CYCLES_PO2..=MAX_CYCLES_PO2` range.
+    pub fn with_max_po2(self, po2: usize) -> Result<Self, Error> {
+        self.with_checked_max_po2(po2, Some(&circuit::v1_3::control_id_by_fn))
     }
 }
 
@@ -95,11 +326,45 @@ impl<SC: CircuitCoreDef, RC: CircuitCoreDef> VerifierContext<SC, RC> {
             suites: BTreeMap::default(),
             segment_verifier_parameters: None,
             succinct_verifier_parameters: None,
+            #[cfg(feature = "groth16")]
+            groth16_verifier_parameters: None,
             circuit,
             recursive_circuit,
+            max_po2: DEFAULT_MAX_PO2,
+            observer: None,
+            strict_claims: false,
+            max_journal_len: None,
+            allowed_control_ids: None,
+            max_segments: None,
+            max_assumptions: None,
         }
     }
 
+    /// Create a [VerifierContext] for custom circuits, with the default hash suites. The segment
+    /// receipts must be proven by one of the `control_ids` and the succinct receipts by a
+    /// recursion program included in `control_root`. The Groth16 verifier parameters are not set.
+    pub fn new(
+        circuit: &'static SC,
+        recursive_circuit: &'static RC,
+        control_ids: impl IntoIterator<Item = Digest>,
+        control_root: Digest,
+    ) -> Self {
+        use risc0_zkp::adapter::PROOF_SYSTEM_INFO;
+        Self::empty(circuit, recursive_circuit)
+            .with_suites(Self::default_hash_suites())
+            .with_segment_verifier_parameters(SegmentReceiptVerifierParameters {
+                control_ids: control_ids.into_iter().collect(),
+                proof_system_info: PROOF_SYSTEM_INFO,
+                circuit_info: SC::CIRCUIT_INFO,
+            })
+            .with_succinct_verifier_parameters(SuccinctReceiptVerifierParameters {
+                control_root,
+                inner_control_root: None,
+                proof_system_info: PROOF_SYSTEM_INFO,
+                circuit_info: RC::CIRCUIT_INFO,
+            })
+    }
+
     /// Return the mapping of hash suites used in the defaul [VerifierContext].
     pub fn default_hash_suites() -> BTreeMap<String, HashSuite<BabyBear>> {
         BTreeMap::from([
@@ -132,4 +397,360 @@ impl<SC: CircuitCoreDef, RC: CircuitCoreDef> VerifierContext<SC, RC> {
         self.succinct_verifier_parameters = Some(params);
         self
     }
+
+    /// Return [VerifierContext] that accepts the succinct receipts of the recursion programs
+    /// included in the given control `root`, instead of the built-in one: e.g. to test alternate
+    /// recursion programs. The succinct verifier parameters are created if not set.
+    pub fn with_control_root(mut self, root: Digest) -> Self {
+        use risc0_zkp::adapter::PROOF_SYSTEM_INFO;
+        match self.succinct_verifier_parameters.as_mut() {
+            Some(params) => params.control_root = root,
+            None => {
+                self.succinct_verifier_parameters = Some(SuccinctReceiptVerifierParameters {
+                    control_root: root,
+                    inner_control_root: None,
+                    proof_system_info: PROOF_SYSTEM_INFO,
+                    circuit_info: RC::CIRCUIT_INFO,
+                })
+            }
+        }
+        self
+    }
+
+    /// Return the recursion control root, the Merkle root over the allowed recursion control IDs,
+    /// enforced by the [SuccinctReceiptVerifierParameters] of this context. It is the same value
+    /// published in the risc0 on-chain verifier contracts. Returns `None` if the succinct
+    /// verifier parameters are not set.
+    pub fn control_root(&self) -> Option<Digest> {
+        self.succinct_verifier_parameters
+            .as_ref()
+            .map(|params| params.control_root)
+    }
+
+    /// The [VerifierVersion] of this context, detected from its [control root](Self::control_root).
+    /// Returns `None` for a custom context.
+    pub fn version(&self) -> Option<VerifierVersion> {
+        self.control_root()
+            .and_then(VerifierVersion::for_control_root)
+    }
+
+    /// Human readable name of this context: the [VerifierVersion] name, like `risc0-v1.2`, or
+    /// `risc0-custom` if the version is unknown.
+    pub fn name(&self) -> &'static str {
+        self.version()
+            .map_or("risc0-custom", |version| version.name())
+    }
+
+    /// The [ProtocolInfo] of the segment circuit, i.e. the circuit version the segment receipts
+    /// are verified against: check it at startup to detect a mismatched dependency.
+    pub fn circuit_info(&self) -> ProtocolInfo {
+        SC::CIRCUIT_INFO
+    }
+
+    /// The [ProtocolInfo] of the recursion circuit, that verifies the succinct receipts.
+    pub fn recursive_circuit_info(&self) -> ProtocolInfo {
+        RC::CIRCUIT_INFO
+    }
+
+    /// The number of output elements of the segment circuit.
+    pub fn circuit_output_size(&self) -> usize {
+        SC::OUTPUT_SIZE
+    }
+
+    /// The number of mix elements of the segment circuit.
+    pub fn circuit_mix_size(&self) -> usize {
+        SC::MIX_SIZE
+    }
+
+    /// Set the max po2 and, if a control ID `resolver` is given, update the segment verifier
+    /// parameters accordingly.
+    fn with_checked_max_po2(
+        mut self,
+        po2: usize,
+        resolver: Option<&dyn Fn(HashFn, usize) -> Option<Digest>>,
+    ) -> Result<Self, Error> {
+        if !(MIN_CYCLES_PO2..=MAX_CYCLES_PO2).contains(&po2) {
+            return Err(Error::Po2OutOfRange {
+                po2,
+                min: MIN_CYCLES_PO2,
+                max: MAX_CYCLES_PO2,
+            });
+        }
+        self.max_po2 = po2;
+        if let Some(resolver) = resolver {
+            self.segment_verifier_parameters = self.segment_verifier_parameters.map(|params| {
+                SegmentReceiptVerifierParameters::from_max_po2(
+                    resolver,
+                    po2,
+                    params.proof_system_info,
+                    params.circuit_info,
+                )
+            });
+        }
+        Ok(self)
+    }
+
+    /// Return [VerifierContext] that notifies the given [VerifyObserver] about the verification
+    /// progress.
+    pub fn with_observer(mut self, observer: impl VerifyObserver + 'static) -> Self {
+        self.observer = Some(Rc::new(observer));
+        self
+    }
+
+    /// Return [VerifierContext] that rejects, or accepts, the receipts with a pruned claim: see
+    /// [`VerifierContext::strict_claims`].
+    pub fn with_strict_claims(mut self, strict: bool) -> Self {
+        self.strict_claims = strict;
+        self
+    }
+
+    /// Return [VerifierContext] that rejects the journals longer than `len` bytes: see
+    /// [`VerifierContext::max_journal_len`].
+    pub fn with_max_journal_len(mut self, len: usize) -> Self {
+        self.max_journal_len = Some(len);
+        self
+    }
+
+    /// Return [VerifierContext] that rejects the composite receipts with more than `max`
+    /// segments: see [`VerifierContext::max_segments`].
+    pub fn with_max_segments(mut self, max: usize) -> Self {
+        self.max_segments = Some(max);
+        self
+    }
+
+    /// Return [VerifierContext] that rejects the composite receipts with more than `max`
+    /// assumption receipts: see [`VerifierContext::max_assumptions`].
+    pub fn with_max_assumptions(mut self, max: usize) -> Self {
+        self.max_assumptions = Some(max);
+        self
+    }
+
+    /// Return [VerifierContext] that accepts only the seals proven with one of the given control
+    /// IDs: see [`VerifierContext::allowed_control_ids`]. The succinct receipts are proven by the
+    /// recursion programs, so their control IDs must be included too to verify them.
+    pub fn with_allowed_control_ids(mut self, ids: impl IntoIterator<Item = Digest>) -> Self {
+        self.allowed_control_ids = Some(ids.into_iter().collect());
+        self
+    }
+
+    /// A copy of this context that notifies `observer` instead of its own observer.
+    pub(crate) fn observed_by(&self, observer: Rc<dyn VerifyObserver>) -> Self {
+        self.copy_with(Some(observer))
+    }
+
+    /// A copy of this context that verifies the receipts of the recursion programs included in
+    /// the guest-provided `control_root` of an assumption: the succinct verifier parameters are
+    /// replaced and the Groth16 ones bound to the same root, while the policy fields (e.g. the
+    /// allowed control IDs, the limits and the observer) are kept.
+    pub(crate) fn for_assumption_root(&self, control_root: Digest) -> Self {
+        use risc0_zkp::adapter::PROOF_SYSTEM_INFO;
+        let mut ctx = self.copy_with(self.observer.clone());
+        ctx.succinct_verifier_parameters = Some(SuccinctReceiptVerifierParameters {
+            control_root,
+            inner_control_root: None,
+            proof_system_info: PROOF_SYSTEM_INFO,
+            circuit_info: RC::CIRCUIT_INFO,
+        });
+        #[cfg(feature = "groth16")]
+        if let Some(params) = ctx.groth16_verifier_parameters.as_mut() {
+            params.control_root = control_root;
+        }
+        ctx
+    }
+
+    fn copy_with(&self, observer: Option<Rc<dyn VerifyObserver>>) -> Self {
+        Self {
+            suites: self.suites.clone(),
+            segment_verifier_parameters: self.segment_verifier_parameters.clone(),
+            succinct_verifier_parameters: self.succinct_verifier_parameters.clone(),
+            #[cfg(feature = "groth16")]
+            groth16_verifier_parameters: self.groth16_verifier_parameters.clone(),
+            circuit: self.circuit,
+            recursive_circuit: self.recursive_circuit,
+            max_po2: self.max_po2,
+            observer,
+            strict_claims: self.strict_claims,
+            max_journal_len: self.max_journal_len,
+            allowed_control_ids: self.allowed_control_ids.clone(),
+            max_segments: self.max_segments,
+            max_assumptions: self.max_assumptions,
+        }
+    }
+
+    /// Notify the observer, if any, about the `result` of the `check`, and return it.
+    pub(crate) fn observe(
+        &self,
+        check: VerifyCheck,
+        result: Result<(), VerificationError>,
+    ) -> Result<(), VerificationError> {
+        if let Some(observer) = &self.observer {
+            observer.on_check(check, &result);
+        }
+        result
+    }
+
+    /// Check `control_id` against [`VerifierContext::allowed_control_ids`].
+    pub(crate) fn is_control_id_allowed(&self, control_id: &Digest) -> bool {
+        self.allowed_control_ids
+            .as_ref()
+            .is_none_or(|ids| ids.contains(control_id))
+    }
+
+    /// Return [VerifierContext] with the given [Groth16ReceiptVerifierParameters] set.
+    #[cfg(feature = "groth16")]
+    pub fn with_groth16_verifier_parameters(
+        mut self,
+        params: Groth16ReceiptVerifierParameters,
+    ) -> Self {
+        self.groth16_verifier_parameters = Some(params);
+        self
+    }
+}
+
+/// The control IDs of a risc0 version by hash function and po2, used to update the segment verifier
+/// parameters to a new max po2.
+type ControlIdResolver = &'static dyn Fn(HashFn, usize) -> Option<Digest>;
+
+/// Builder of a [VerifierContext]: it starts from a built-in context, e.g.
+/// [`VerifierContextBuilder::v1_2()`], accumulates the options and applies them all in
+/// [`VerifierContextBuilder::build()`], like chaining the `with_*` methods of the context would.
+///
+/// A custom context can be the base too, through [From]: in that case the max po2 just bounds the
+/// segment size, without updating the segment control IDs.
+pub struct VerifierContextBuilder<SC: CircuitCoreDef, RC: CircuitCoreDef> {
+    ctx: VerifierContext<SC, RC>,
+    resolver: Option<ControlIdResolver>,
+    max_po2: Option<usize>,
+    hash_fns: Option<BTreeSet<HashFn>>,
+}
+
+impl VerifierContextBuilder<circuit::v1_0::CircuitImpl, circuit::v1_0::recursive::CircuitImpl> {
+    /// Start from [`VerifierContext::v1_0()`].
+    pub fn v1_0() -> Self {
+        Self::new(VerifierContext::v1_0(), None)
+    }
+}
+
+impl VerifierContextBuilder<circuit::v1_1::CircuitImpl, circuit::v1_1::recursive::CircuitImpl> {
+    /// Start from [`VerifierContext::v1_1()`].
+    pub fn v1_1() -> Self {
+        Self::new(
+            VerifierContext::v1_1(),
+            Some(&circuit::v1_1::control_id_by_fn),
+        )
+    }
+}
+
+impl VerifierContextBuilder<circuit::v1_2::CircuitImpl, circuit::v1_2::recursive::CircuitImpl> {
+    /// Start from [`VerifierContext::v1_2()`].
+    pub fn v1_2() -> Self {
+        Self::new(
+            VerifierContext::v1_2(),
+            Some(&circuit::v1_2::control_id_by_fn),
+        )
+    }
+}
+
+#[cfg(feature = "v1_3")]
+impl VerifierContextBuilder<circuit::v1_3::CircuitImpl, circuit::v1_3::recursive::CircuitImpl> {
+    /// Start from [`VerifierContext::v1_3()`].
+    pub fn v1_3() -> Self {
+        Self::new(
+            VerifierContext::v1_3(),
+            Some(&circuit::v1_3::control_id_by_fn),
+        )
+    }
+}
+
+impl<SC: CircuitCoreDef, RC: CircuitCoreDef> From<VerifierContext<SC, RC>>
+    for VerifierContextBuilder<SC, RC>
+{
+    fn from(ctx: VerifierContext<SC, RC>) -> Self {
+        Self::new(ctx, None)
+    }
+}
+
+impl<SC: CircuitCoreDef, RC: CircuitCoreDef> VerifierContextBuilder<SC, RC> {
+    fn new(ctx: VerifierContext<SC, RC>, resolver: Option<ControlIdResolver>) -> Self {
+        Self {
+            ctx,
+            resolver,
+            max_po2: None,
+            hash_fns: None,
+        }
+    }
+
+    /// Accept segments up to the given size, as a power of two (po2), like the `with_max_po2()`
+    /// of the built-in contexts. The range is checked by [`VerifierContextBuilder::build()`].
+    pub fn max_po2(mut self, po2: usize) -> Self {
+        self.max_po2 = Some(po2);
+        self
+    }
+
+    /// Keep only the hash suites of the given hash functions, e.g. to reject the `blake2b` seals.
+    pub fn hash_fns(mut self, hash_fns: impl IntoIterator<Item = HashFn>) -> Self {
+        self.hash_fns = Some(hash_fns.into_iter().collect());
+        self
+    }
+
+    /// Accept only the seals proven with one of the given control IDs: see
+    /// [`VerifierContext::with_allowed_control_ids()`].
+    pub fn allowed_control_ids(mut self, ids: impl IntoIterator<Item = Digest>) -> Self {
+        self.ctx = self.ctx.with_allowed_control_ids(ids);
+        self
+    }
+
+    /// Accept the succinct receipts of the recursion programs included in the given control
+    /// `root`: see [`VerifierContext::with_control_root()`].
+    pub fn control_root(mut self, root: Digest) -> Self {
+        self.ctx = self.ctx.with_control_root(root);
+        self
+    }
+
+    /// Reject, or accept, the receipts with a pruned claim: see
+    /// [`VerifierContext::strict_claims`].
+    pub fn strict_claims(mut self, strict: bool) -> Self {
+        self.ctx = self.ctx.with_strict_claims(strict);
+        self
+    }
+
+    /// Reject the journals longer than `len` bytes: see [`VerifierContext::max_journal_len`].
+    pub fn max_journal_len(mut self, len: usize) -> Self {
+        self.ctx = self.ctx.with_max_journal_len(len);
+        self
+    }
+
+    /// Reject the composite receipts with more than `max` segments: see
+    /// [`VerifierContext::max_segments`].
+    pub fn max_segments(mut self, max: usize) -> Self {
+        self.ctx = self.ctx.with_max_segments(max);
+        self
+    }
+
+    /// Reject the composite receipts with more than `max` assumption receipts: see
+    /// [`VerifierContext::max_assumptions`].
+    pub fn max_assumptions(mut self, max: usize) -> Self {
+        self.ctx = self.ctx.with_max_assumptions(max);
+        self
+    }
+
+    /// Notify the given [VerifyObserver] about the verification progress.
+    pub fn observer(mut self, observer: impl VerifyObserver + 'static) -> Self {
+        self.ctx = self.ctx.with_observer(observer);
+        self
+    }
+
+    /// Build the [VerifierContext]. Fails with [Error::Po2OutOfRange] if the max po2 is outside
+    /// the `MIN_CYCLES_PO2..=MAX_CYCLES_PO2` range.
+    pub fn build(self) -> Result<VerifierContext<SC, RC>, Error> {
+        let mut ctx = match self.max_po2 {
+            Some(po2) => self.ctx.with_checked_max_po2(po2, self.resolver)?,
+            None => self.ctx,
+        };
+        if let Some(hash_fns) = self.hash_fns {
+            ctx.suites
+                .retain(|name, _| hash_fns.iter().any(|hash_fn| hash_fn.name() == name));
+        }
+        Ok(ctx)
+    }
 }
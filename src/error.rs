@@ -0,0 +1,199 @@
+// Copyright 2024, Horizen Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Crate level errors.
+//!
+//! Cryptographic failures are still reported with the risc0 [`VerificationError`], while [`Error`]
+//! wraps it and adds the failures that are specific to this crate's API.
+
+use core::fmt;
+
+use alloc::vec::Vec;
+
+use risc0_binfmt::ExitCode;
+use risc0_zkp::{core::digest::Digest, verify::VerificationError};
+
+use crate::VerifierVersion;
+
+/// Error returned by the verification methods that need more context than a plain
+/// [`VerificationError`].
+#[derive(Debug, PartialEq)]
+#[non_exhaustive]
+pub enum Error {
+    /// The proof failed the verification.
+    Verification(VerificationError),
+    /// The receipt claim is pruned: only its digest is available.
+    PrunedClaim(Digest),
+    /// No image ID was provided to verify against.
+    EmptyImageIds,
+    /// The verifier parameters digest doesn't match any known verifier version.
+    UnknownVerifierParameters(Digest),
+    /// The segment size, as a power of two (po2), is outside the supported `min..=max` range.
+    Po2OutOfRange {
+        /// The requested po2.
+        po2: usize,
+        /// The minimum supported po2.
+        min: usize,
+        /// The maximum supported po2.
+        max: usize,
+    },
+    /// A segment of a composite receipt is bigger than the maximum segment size of the context.
+    SegmentPo2TooLarge {
+        /// The index of the segment.
+        index: u32,
+        /// The po2 of the segment.
+        po2: usize,
+        /// The maximum po2 accepted by the context.
+        max: usize,
+    },
+    /// A composite receipt exceeds a limit of the context, e.g.
+    /// [`VerifierContext::max_segments`](crate::VerifierContext::max_segments).
+    LimitExceeded {
+        /// The exceeded limit.
+        kind: LimitKind,
+        /// The maximum accepted by the context.
+        limit: usize,
+        /// The number found in the receipt.
+        found: usize,
+    },
+    /// The proof is valid and matches the image ID and the journal, but the guest terminated
+    /// with an unexpected exit code.
+    UnexpectedExitCode {
+        /// The exit code required by the verification.
+        expected: ExitCode,
+        /// The exit code of the proven execution.
+        found: ExitCode,
+    },
+    /// The seals are accepted by more than one of the verifier versions, in this order: the
+    /// version of the proof is unknown.
+    AmbiguousVersion(Vec<VerifierVersion>),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Verification(_) => write!(f, "verification failed"),
+            Self::PrunedClaim(digest) => write!(f, "receipt claim is pruned: {digest}"),
+            Self::EmptyImageIds => write!(f, "no image id to verify against"),
+            Self::UnknownVerifierParameters(digest) => {
+                write!(f, "unknown verifier parameters: {digest}")
+            }
+            Self::Po2OutOfRange { po2, min, max } => {
+                write!(f, "po2 {po2} is out of the supported range {min}..={max}")
+            }
+            Self::SegmentPo2TooLarge { index, po2, max } => {
+                write!(
+                    f,
+                    "segment {index} has po2 {po2}, greater than the max {max}"
+                )
+            }
+            Self::LimitExceeded { kind, limit, found } => {
+                write!(f, "{found} {kind} exceed the limit {limit}")
+            }
+            Self::UnexpectedExitCode { expected, found } => {
+                write!(f, "unexpected exit code {found:?}; expected {expected:?}")
+            }
+            Self::AmbiguousVersion(versions) => {
+                write!(f, "the proof verifies with more than one version")?;
+                versions
+                    .iter()
+                    .enumerate()
+                    .try_for_each(|(i, version)| match i {
+                        0 => write!(f, ": {version}"),
+                        _ => write!(f, ", {version}"),
+                    })
+            }
+        }
+    }
+}
+
+/// The limits of a [`VerifierContext`](crate::VerifierContext) reported by
+/// [`Error::LimitExceeded`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum LimitKind {
+    /// The segments of a composite receipt.
+    Segments,
+    /// The assumption receipts of a composite receipt.
+    Assumptions,
+}
+
+impl fmt::Display for LimitKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Self::Segments => "segments",
+            Self::Assumptions => "assumptions",
+        })
+    }
+}
+
+/// The [`Error::Verification`] failures expose the wrapped [`VerificationError`] as their source,
+/// that is not repeated in their message.
+#[cfg(feature = "std")]
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Verification(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<VerificationError> for Error {
+    fn from(e: VerificationError) -> Self {
+        Self::Verification(e)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::ToString;
+    use std::error::Error as _;
+
+    #[test]
+    fn verification_error_is_the_source() {
+        let error = Error::from(VerificationError::InvalidProof);
+
+        assert_eq!(
+            error.source().unwrap().downcast_ref(),
+            Some(&VerificationError::InvalidProof)
+        );
+        assert_eq!(error.to_string(), "verification failed");
+        assert!(Error::EmptyImageIds.source().is_none());
+    }
+
+    #[test]
+    fn ambiguous_version_lists_the_versions() {
+        let error =
+            Error::AmbiguousVersion(alloc::vec![VerifierVersion::V1_1, VerifierVersion::V1_2]);
+
+        assert_eq!(
+            error.to_string(),
+            "the proof verifies with more than one version: risc0-v1.1, risc0-v1.2"
+        );
+    }
+
+    #[test]
+    fn anyhow_keeps_the_cause() {
+        let error = anyhow::Error::from(Error::from(VerificationError::InvalidProof));
+
+        assert_eq!(
+            error.root_cause().downcast_ref(),
+            Some(&VerificationError::InvalidProof)
+        );
+        assert_eq!(error.chain().count(), 2);
+    }
+}
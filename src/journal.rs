@@ -0,0 +1,265 @@
+// Copyright Copyright 2024, Horizen Labs, Inc.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Encoding and decoding of the typed values committed by the guest in the
+//! [Journal][crate::Journal].
+//!
+//! The risc0 guests commit the values with a word oriented encoding (`risc0_zkvm::serde`): every
+//! primitive up to 32 bits takes a whole `u32` word, 64 bits values take two words, and byte
+//! arrays and strings are prefixed by their length and padded to the word boundary.
+//!
+//! ```
+//! use risc0_verifier::{journal::decode_from_slice, Journal};
+//!
+//! // `(7_u64, true)` committed by the guest
+//! let journal = Journal::new([7_u32, 0, 1].iter().flat_map(|w| w.to_le_bytes()).collect());
+//!
+//! let value: (u64, bool) = journal.decode().unwrap();
+//! assert_eq!(value, (7, true));
+//! assert_eq!(decode_from_slice::<(u64, bool)>(&journal.bytes).unwrap(), value);
+//! assert_eq!(Journal::encode(&value).unwrap(), journal);
+//! ```
+
+use alloc::{string::String, vec::Vec};
+use core::fmt;
+
+use serde::{de::DeserializeOwned, Serialize};
+
+mod deserializer;
+mod serializer;
+
+const WORD_SIZE: usize = core::mem::size_of::<u32>();
+
+/// Error returned when decoding a typed value from the journal fails.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JournalDecodeError {
+    /// The value was decoded but some data remain in the journal.
+    TrailingBytes,
+    /// The journal ended before the value was completely decoded.
+    UnexpectedEof,
+    /// The journal content is not a valid encoding of the requested type.
+    Serde(String),
+}
+
+impl fmt::Display for JournalDecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::TrailingBytes => write!(f, "failed to decode journal: trailing bytes"),
+            Self::UnexpectedEof => write!(f, "failed to decode journal: unexpected end of data"),
+            Self::Serde(e) => write!(f, "failed to decode journal: {e}"),
+        }
+    }
+}
+
+impl serde::de::Error for JournalDecodeError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        use alloc::string::ToString;
+        Self::Serde(msg.to_string())
+    }
+}
+
+impl serde::de::StdError for JournalDecodeError {}
+
+/// Error returned when encoding a typed value into a journal fails.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JournalEncodeError {
+    /// A sequence or a map doesn't declare its length up front.
+    UnknownLength,
+    /// The value can't be represented with the journal encoding.
+    Serde(String),
+}
+
+impl fmt::Display for JournalEncodeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::UnknownLength => write!(f, "failed to encode journal: unknown sequence length"),
+            Self::Serde(e) => write!(f, "failed to encode journal: {e}"),
+        }
+    }
+}
+
+impl serde::ser::Error for JournalEncodeError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        use alloc::string::ToString;
+        Self::Serde(msg.to_string())
+    }
+}
+
+impl serde::ser::StdError for JournalEncodeError {}
+
+/// Encode `value` as the guest would commit it in the journal: [decode_from_slice] is the
+/// inverse of this function.
+pub fn encode_to_vec<T: Serialize + ?Sized>(value: &T) -> Result<Vec<u8>, JournalEncodeError> {
+    let mut serializer = serializer::Serializer::default();
+    value.serialize(&mut serializer)?;
+    Ok(serializer
+        .into_words()
+        .into_iter()
+        .flat_map(u32::to_le_bytes)
+        .collect())
+}
+
+/// Decode a value of type `T` from the raw journal `bytes`: all the bytes should be consumed.
+pub fn decode_from_slice<T: DeserializeOwned>(bytes: &[u8]) -> Result<T, JournalDecodeError> {
+    // The journal is word aligned, but the guest can also commit raw bytes: in this case the last
+    // word is zero padded.
+    let words: Vec<u32> = bytes
+        .chunks(WORD_SIZE)
+        .map(|chunk| {
+            let mut word = [0_u8; WORD_SIZE];
+            word[..chunk.len()].copy_from_slice(chunk);
+            u32::from_le_bytes(word)
+        })
+        .collect();
+    let mut deserializer = deserializer::Deserializer::new(&words);
+    let value = T::deserialize(&mut deserializer)?;
+    if !deserializer.is_empty() {
+        return Err(JournalDecodeError::TrailingBytes);
+    }
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::{string::ToString, vec};
+    use serde::{Deserialize, Serialize};
+
+    fn to_bytes(words: &[u32]) -> Vec<u8> {
+        words.iter().flat_map(|w| w.to_le_bytes()).collect()
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Receipt {
+        amount: u64,
+        recipient: [u8; 20],
+    }
+
+    #[test]
+    fn should_decode_struct() {
+        let mut words = vec![0x89abcdef, 0x01234567];
+        words.extend(1..=20);
+
+        let receipt: Receipt = decode_from_slice(&to_bytes(&words)).unwrap();
+
+        assert_eq!(
+            receipt,
+            Receipt {
+                amount: 0x0123456789abcdef,
+                recipient: core::array::from_fn(|i| i as u8 + 1),
+            }
+        );
+    }
+
+    #[test]
+    fn should_decode_strings_and_vectors() {
+        let words = [1, 0x00000061, 3, 0x00636261, 2, 7, 8];
+
+        let decoded: (String, String, Vec<u8>) = decode_from_slice(&to_bytes(&words)).unwrap();
+
+        assert_eq!(decoded, ("a".to_string(), "abc".to_string(), vec![7, 8]));
+    }
+
+    #[test]
+    fn should_reject_trailing_bytes() {
+        assert_eq!(
+            decode_from_slice::<u32>(&to_bytes(&[1, 2])),
+            Err(JournalDecodeError::TrailingBytes)
+        );
+    }
+
+    #[test]
+    fn should_reject_truncated_journal() {
+        assert_eq!(
+            decode_from_slice::<u64>(&to_bytes(&[1])),
+            Err(JournalDecodeError::UnexpectedEof)
+        );
+        assert_eq!(
+            decode_from_slice::<String>(&to_bytes(&[12, 0x00636261])),
+            Err(JournalDecodeError::UnexpectedEof)
+        );
+    }
+
+    #[test]
+    fn should_reject_invalid_values() {
+        assert!(matches!(
+            decode_from_slice::<bool>(&to_bytes(&[2])),
+            Err(JournalDecodeError::Serde(_))
+        ));
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    enum Command {
+        Stop,
+        Move(i16, i16),
+        Say { text: String },
+    }
+
+    fn round_trip<T: Serialize + DeserializeOwned + PartialEq + core::fmt::Debug>(value: T) {
+        let bytes = encode_to_vec(&value).unwrap();
+
+        assert_eq!(bytes.len() % WORD_SIZE, 0);
+        assert_eq!(decode_from_slice::<T>(&bytes).unwrap(), value);
+    }
+
+    #[test]
+    fn should_round_trip_primitives() {
+        round_trip(true);
+        round_trip(0xab_u8);
+        round_trip(-3_i16);
+        round_trip(u32::MAX);
+        round_trip(i64::MIN);
+        round_trip(u128::MAX - 1);
+        round_trip(-2.5_f64);
+        round_trip('λ');
+        round_trip(Some(42_u64));
+        round_trip(Option::<u8>::None);
+    }
+
+    #[test]
+    fn should_round_trip_compound_values() {
+        round_trip((7_u64, false, "abcde".to_string()));
+        round_trip(vec![1_u8, 2, 3, 4, 5]);
+        round_trip(Receipt {
+            amount: 1 << 40,
+            recipient: [0xaa; 20],
+        });
+        round_trip(vec![
+            Command::Stop,
+            Command::Move(-1, 2),
+            Command::Say {
+                text: "hi".to_string(),
+            },
+        ]);
+    }
+
+    #[test]
+    fn should_encode_as_the_guest() {
+        let mut words = vec![0x89abcdef, 0x01234567];
+        words.extend(1..=20);
+        let receipt = Receipt {
+            amount: 0x0123456789abcdef,
+            recipient: core::array::from_fn(|i| i as u8 + 1),
+        };
+
+        assert_eq!(encode_to_vec(&receipt).unwrap(), to_bytes(&words));
+        assert_eq!(
+            encode_to_vec(&("a", "abc", [7_u8, 8].as_slice())).unwrap(),
+            to_bytes(&[1, 0x00000061, 3, 0x00636261, 2, 7, 8])
+        );
+    }
+}
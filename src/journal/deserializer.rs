@@ -0,0 +1,367 @@
+// Copyright Copyright 2024, Horizen Labs, Inc.
+// Copyright Copyright 2024 RISC Zero, Inc.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+use alloc::{string::String, vec};
+
+use serde::de::{DeserializeSeed, IntoDeserializer, Visitor};
+
+use super::{JournalDecodeError, WORD_SIZE};
+
+type Result<T> = core::result::Result<T, JournalDecodeError>;
+
+/// A deserializer for the risc0 word oriented encoding: the one used by the guests to commit
+/// values in the journal.
+pub(crate) struct Deserializer<'de> {
+    words: &'de [u32],
+}
+
+impl<'de> Deserializer<'de> {
+    pub(crate) fn new(words: &'de [u32]) -> Self {
+        Self { words }
+    }
+
+    /// Returns `true` if all the input words were consumed.
+    pub(crate) fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    fn try_take_word(&mut self) -> Result<u32> {
+        let (word, rest) = self
+            .words
+            .split_first()
+            .ok_or(JournalDecodeError::UnexpectedEof)?;
+        self.words = rest;
+        Ok(*word)
+    }
+
+    fn try_take_dword(&mut self) -> Result<u64> {
+        let low = self.try_take_word()? as u64;
+        let high = self.try_take_word()? as u64;
+        Ok(low | (high << 32))
+    }
+
+    /// Fill the given buffer with bytes from input, and discard the padding up to the next word
+    /// boundary.
+    fn read_padded_bytes(&mut self, out: &mut [u8]) -> Result<()> {
+        let words = out.len().div_ceil(WORD_SIZE);
+        if words > self.words.len() {
+            return Err(JournalDecodeError::UnexpectedEof);
+        }
+        let (head, rest) = self.words.split_at(words);
+        let bytes: &[u8] = bytemuck::cast_slice(head);
+        out.copy_from_slice(&bytes[..out.len()]);
+        self.words = rest;
+        Ok(())
+    }
+
+    fn take_bytes(&mut self) -> Result<vec::Vec<u8>> {
+        let len_bytes = self.try_take_word()? as usize;
+        if len_bytes.div_ceil(WORD_SIZE) > self.words.len() {
+            return Err(JournalDecodeError::UnexpectedEof);
+        }
+        let mut bytes = vec![0u8; len_bytes];
+        self.read_padded_bytes(&mut bytes)?;
+        Ok(bytes)
+    }
+}
+
+struct SeqAccess<'a, 'de> {
+    deserializer: &'a mut Deserializer<'de>,
+    len: usize,
+}
+
+impl<'de> serde::de::SeqAccess<'de> for SeqAccess<'_, 'de> {
+    type Error = JournalDecodeError;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        if self.len > 0 {
+            self.len -= 1;
+            Ok(Some(DeserializeSeed::deserialize(
+                seed,
+                &mut *self.deserializer,
+            )?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        // Don't trust the declared length to preallocate more than the available words.
+        Some(self.len.min(self.deserializer.words.len()))
+    }
+}
+
+struct MapAccess<'a, 'de> {
+    deserializer: &'a mut Deserializer<'de>,
+    len: usize,
+}
+
+impl<'de> serde::de::MapAccess<'de> for MapAccess<'_, 'de> {
+    type Error = JournalDecodeError;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(&mut self, seed: K) -> Result<Option<K::Value>> {
+        if self.len > 0 {
+            self.len -= 1;
+            Ok(Some(DeserializeSeed::deserialize(
+                seed,
+                &mut *self.deserializer,
+            )?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value> {
+        DeserializeSeed::deserialize(seed, &mut *self.deserializer)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.len.min(self.deserializer.words.len()))
+    }
+}
+
+impl<'de> serde::de::VariantAccess<'de> for &mut Deserializer<'de> {
+    type Error = JournalDecodeError;
+
+    fn unit_variant(self) -> Result<()> {
+        Ok(())
+    }
+
+    fn newtype_variant_seed<V: DeserializeSeed<'de>>(self, seed: V) -> Result<V::Value> {
+        DeserializeSeed::deserialize(seed, self)
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(self, len: usize, visitor: V) -> Result<V::Value> {
+        serde::de::Deserializer::deserialize_tuple(self, len, visitor)
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value> {
+        serde::de::Deserializer::deserialize_tuple(self, fields.len(), visitor)
+    }
+}
+
+impl<'de> serde::de::EnumAccess<'de> for &mut Deserializer<'de> {
+    type Error = JournalDecodeError;
+    type Variant = Self;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(self, seed: V) -> Result<(V::Value, Self)> {
+        let tag = self.try_take_word()?;
+        let val = DeserializeSeed::deserialize(seed, tag.into_deserializer())?;
+        Ok((val, self))
+    }
+}
+
+impl<'de> serde::Deserializer<'de> for &mut Deserializer<'de> {
+    type Error = JournalDecodeError;
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+
+    fn deserialize_any<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value> {
+        Err(JournalDecodeError::Serde(
+            "deserialize_any is not supported".into(),
+        ))
+    }
+
+    fn deserialize_bool<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        let val = match self.try_take_word()? {
+            0 => false,
+            1 => true,
+            _ => return Err(JournalDecodeError::Serde("found an invalid bool".into())),
+        };
+        visitor.visit_bool(val)
+    }
+
+    fn deserialize_i8<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_i32(self.try_take_word()? as i32)
+    }
+
+    fn deserialize_i16<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_i32(self.try_take_word()? as i32)
+    }
+
+    fn deserialize_i32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_i32(self.try_take_word()? as i32)
+    }
+
+    fn deserialize_i64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_i64(self.try_take_dword()? as i64)
+    }
+
+    fn deserialize_i128<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        let mut bytes = [0u8; 16];
+        self.read_padded_bytes(&mut bytes)?;
+        visitor.visit_i128(i128::from_le_bytes(bytes))
+    }
+
+    fn deserialize_u8<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_u32(self.try_take_word()?)
+    }
+
+    fn deserialize_u16<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_u32(self.try_take_word()?)
+    }
+
+    fn deserialize_u32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_u32(self.try_take_word()?)
+    }
+
+    fn deserialize_u64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_u64(self.try_take_dword()?)
+    }
+
+    fn deserialize_u128<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        let mut bytes = [0u8; 16];
+        self.read_padded_bytes(&mut bytes)?;
+        visitor.visit_u128(u128::from_le_bytes(bytes))
+    }
+
+    fn deserialize_f32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_f32(f32::from_bits(self.try_take_word()?))
+    }
+
+    fn deserialize_f64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_f64(f64::from_bits(self.try_take_dword()?))
+    }
+
+    fn deserialize_char<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        let c = char::from_u32(self.try_take_word()?)
+            .ok_or_else(|| JournalDecodeError::Serde("found an invalid unicode char".into()))?;
+        visitor.visit_char(c)
+    }
+
+    fn deserialize_str<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        let bytes = self.take_bytes()?;
+        visitor.visit_string(
+            String::from_utf8(bytes)
+                .map_err(|_| JournalDecodeError::Serde("found an invalid utf-8 string".into()))?,
+        )
+    }
+
+    fn deserialize_string<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_bytes<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_byte_buf(self.take_bytes()?)
+    }
+
+    fn deserialize_byte_buf<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        self.deserialize_bytes(visitor)
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        match self.try_take_word()? {
+            0 => visitor.visit_none(),
+            1 => visitor.visit_some(self),
+            _ => Err(JournalDecodeError::Serde(
+                "found an invalid option discriminant".into(),
+            )),
+        }
+    }
+
+    fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value> {
+        self.deserialize_unit(visitor)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        let len = self.try_take_word()? as usize;
+        visitor.visit_seq(SeqAccess {
+            deserializer: self,
+            len,
+        })
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(self, len: usize, visitor: V) -> Result<V::Value> {
+        visitor.visit_seq(SeqAccess {
+            deserializer: self,
+            len,
+        })
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value> {
+        self.deserialize_tuple(len, visitor)
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        let len = self.try_take_word()? as usize;
+        visitor.visit_map(MapAccess {
+            deserializer: self,
+            len,
+        })
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value> {
+        self.deserialize_tuple(fields.len(), visitor)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value> {
+        visitor.visit_enum(self)
+    }
+
+    fn deserialize_identifier<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value> {
+        Err(JournalDecodeError::Serde(
+            "deserialize_identifier is not supported".into(),
+        ))
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value> {
+        Err(JournalDecodeError::Serde(
+            "deserialize_ignored_any is not supported".into(),
+        ))
+    }
+}
@@ -0,0 +1,346 @@
+// Copyright Copyright 2024, Horizen Labs, Inc.
+// Copyright Copyright 2024 RISC Zero, Inc.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+use alloc::vec::Vec;
+
+use serde::ser::{
+    Serialize, SerializeMap, SerializeSeq, SerializeStruct, SerializeStructVariant, SerializeTuple,
+    SerializeTupleStruct, SerializeTupleVariant,
+};
+
+use super::{JournalEncodeError, WORD_SIZE};
+
+type Result<T> = core::result::Result<T, JournalEncodeError>;
+
+/// A serializer for the risc0 word oriented encoding: the one used by the guests to commit
+/// values in the journal.
+#[derive(Default)]
+pub(crate) struct Serializer {
+    words: Vec<u32>,
+}
+
+impl Serializer {
+    /// Consume the serializer and return the encoded words.
+    pub(crate) fn into_words(self) -> Vec<u32> {
+        self.words
+    }
+
+    fn put_word(&mut self, word: u32) {
+        self.words.push(word);
+    }
+
+    fn put_dword(&mut self, dword: u64) {
+        self.put_word(dword as u32);
+        self.put_word((dword >> 32) as u32);
+    }
+
+    /// Write the given bytes, zero padding them up to the next word boundary.
+    fn write_padded_bytes(&mut self, bytes: &[u8]) {
+        self.words.extend(bytes.chunks(WORD_SIZE).map(|chunk| {
+            let mut word = [0_u8; WORD_SIZE];
+            word[..chunk.len()].copy_from_slice(chunk);
+            u32::from_le_bytes(word)
+        }));
+    }
+
+    fn put_bytes(&mut self, bytes: &[u8]) -> Result<()> {
+        self.put_word(Self::len_word(bytes.len())?);
+        self.write_padded_bytes(bytes);
+        Ok(())
+    }
+
+    fn len_word(len: usize) -> Result<u32> {
+        u32::try_from(len).map_err(|_| JournalEncodeError::Serde("length overflows u32".into()))
+    }
+}
+
+impl serde::Serializer for &mut Serializer {
+    type Ok = ();
+    type Error = JournalEncodeError;
+    type SerializeSeq = Self;
+    type SerializeTuple = Self;
+    type SerializeTupleStruct = Self;
+    type SerializeTupleVariant = Self;
+    type SerializeMap = Self;
+    type SerializeStruct = Self;
+    type SerializeStructVariant = Self;
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+
+    fn serialize_bool(self, v: bool) -> Result<()> {
+        self.serialize_u32(v as u32)
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<()> {
+        self.serialize_i32(v as i32)
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<()> {
+        self.serialize_i32(v as i32)
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<()> {
+        self.serialize_u32(v as u32)
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<()> {
+        self.serialize_u64(v as u64)
+    }
+
+    fn serialize_i128(self, v: i128) -> Result<()> {
+        self.write_padded_bytes(&v.to_le_bytes());
+        Ok(())
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<()> {
+        self.serialize_u32(v as u32)
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<()> {
+        self.serialize_u32(v as u32)
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<()> {
+        self.put_word(v);
+        Ok(())
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<()> {
+        self.put_dword(v);
+        Ok(())
+    }
+
+    fn serialize_u128(self, v: u128) -> Result<()> {
+        self.write_padded_bytes(&v.to_le_bytes());
+        Ok(())
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<()> {
+        self.serialize_u32(v.to_bits())
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<()> {
+        self.serialize_u64(v.to_bits())
+    }
+
+    fn serialize_char(self, v: char) -> Result<()> {
+        self.serialize_u32(v as u32)
+    }
+
+    fn serialize_str(self, v: &str) -> Result<()> {
+        self.put_bytes(v.as_bytes())
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<()> {
+        self.put_bytes(v)
+    }
+
+    fn serialize_none(self) -> Result<()> {
+        self.serialize_u32(0)
+    }
+
+    fn serialize_some<T: ?Sized + Serialize>(self, value: &T) -> Result<()> {
+        self.serialize_u32(1)?;
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<()> {
+        Ok(())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<()> {
+        self.serialize_unit()
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        _variant: &'static str,
+    ) -> Result<()> {
+        self.serialize_u32(variant_index)
+    }
+
+    fn serialize_newtype_struct<T: ?Sized + Serialize>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<()> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: ?Sized + Serialize>(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        _variant: &'static str,
+        value: &T,
+    ) -> Result<()> {
+        self.serialize_u32(variant_index)?;
+        value.serialize(self)
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<Self> {
+        let len = len.ok_or(JournalEncodeError::UnknownLength)?;
+        self.put_word(Serializer::len_word(len)?);
+        Ok(self)
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Self> {
+        Ok(self)
+    }
+
+    fn serialize_tuple_struct(self, _name: &'static str, _len: usize) -> Result<Self> {
+        Ok(self)
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self> {
+        self.put_word(variant_index);
+        Ok(self)
+    }
+
+    fn serialize_map(self, len: Option<usize>) -> Result<Self> {
+        self.serialize_seq(len)
+    }
+
+    fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<Self> {
+        Ok(self)
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self> {
+        self.put_word(variant_index);
+        Ok(self)
+    }
+}
+
+impl SerializeSeq for &mut Serializer {
+    type Ok = ();
+    type Error = JournalEncodeError;
+
+    fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<()> {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl SerializeTuple for &mut Serializer {
+    type Ok = ();
+    type Error = JournalEncodeError;
+
+    fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<()> {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl SerializeTupleStruct for &mut Serializer {
+    type Ok = ();
+    type Error = JournalEncodeError;
+
+    fn serialize_field<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<()> {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl SerializeTupleVariant for &mut Serializer {
+    type Ok = ();
+    type Error = JournalEncodeError;
+
+    fn serialize_field<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<()> {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl SerializeMap for &mut Serializer {
+    type Ok = ();
+    type Error = JournalEncodeError;
+
+    fn serialize_key<T: ?Sized + Serialize>(&mut self, key: &T) -> Result<()> {
+        key.serialize(&mut **self)
+    }
+
+    fn serialize_value<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<()> {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl SerializeStruct for &mut Serializer {
+    type Ok = ();
+    type Error = JournalEncodeError;
+
+    fn serialize_field<T: ?Sized + Serialize>(
+        &mut self,
+        _key: &'static str,
+        value: &T,
+    ) -> Result<()> {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl SerializeStructVariant for &mut Serializer {
+    type Ok = ();
+    type Error = JournalEncodeError;
+
+    fn serialize_field<T: ?Sized + Serialize>(
+        &mut self,
+        _key: &'static str,
+        value: &T,
+    ) -> Result<()> {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<()> {
+        Ok(())
+    }
+}
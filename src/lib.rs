@@ -16,31 +16,64 @@
 #![no_std]
 #![doc = include_str!("../README.md")]
 
-#[cfg(test)]
+#[cfg(any(test, feature = "std"))]
 extern crate std;
 
+// The fake receipts prove nothing: never let them reach a release build.
+#[cfg(all(feature = "unsafe-fake", not(debug_assertions)))]
+compile_error!("the `unsafe-fake` feature is available only in debug builds");
+
 extern crate alloc;
 extern crate core;
 
+#[macro_use]
+mod logging;
+
 pub use circuit::CircuitCoreDef;
-pub use context::VerifierContext;
+pub use context::{VerifierContext, VerifierContextBuilder, VerifierVersion};
+pub use error::{Error, LimitKind};
+pub use journal::{JournalDecodeError, JournalEncodeError};
 pub use key::Vk;
+pub use observer::{VerifyCheck, VerifyObserver, VerifyStep};
+#[cfg(feature = "unsafe-fake")]
+pub use receipt::fake::FakeReceipt;
+#[cfg(feature = "groth16")]
+pub use receipt::groth16::{Groth16Receipt, Groth16ReceiptVerifierParameters};
 pub use receipt::{
-    composite::CompositeReceipt, succinct::SuccinctReceipt, InnerReceipt, Journal, Proof,
+    composite::CompositeReceipt, merkle::MerkleProof, security_bits, succinct::SuccinctReceipt,
+    InnerAssumptionReceipt, InnerReceipt, Journal, Proof, ProofHeader, ReceiptKind, VerifiedFacts,
+    DEFAULT_MAX_PO2,
+};
+pub use receipt_claim::{
+    claim_digest_for, Assumption, Assumptions, ClaimDiff, ClaimFields, ExitCodeExt, MaybePruned,
+    Mismatch, Output, PrunedValueError, ReceiptClaim, ReceiptClaimBuilder,
 };
-pub use receipt_claim::{MaybePruned, ReceiptClaim};
-pub use sha::{Digest, Digestible};
+pub use segment::SegmentReceipt;
+pub use ser::{Limits, SerError, PROOF_FORMAT_MAGIC, PROOF_FORMAT_VERSION};
+pub use sha::{digest_from_hex, digest_to_hex, Digest, DigestParseError, Digestible};
 
-pub use risc0_zkp::verify::VerificationError;
+pub use receipt::merkle;
+pub use risc0_binfmt::{ExitCode, SystemState};
+pub use risc0_zkp::{adapter::ProtocolInfo, verify::VerificationError};
 
-mod circuit;
+#[cfg(feature = "capi")]
+pub mod capi;
+pub mod circuit;
 mod context;
+mod error;
+pub mod journal;
 mod key;
+mod observer;
 pub mod poseidon2_injection;
 mod receipt;
 mod receipt_claim;
 mod segment;
+mod ser;
 pub mod sha;
+#[cfg(all(test, any(feature = "capi", feature = "wasm")))]
+mod test_utils;
+#[cfg(feature = "wasm")]
+pub mod wasm;
 
 /// Verifies the given `proof` and public inputs `pubs` using the verification key `vk` within the provided
 /// `VerifierContext`. The context identifies the prover version used to generate the proof. Refer to [`VerifierContext`]
@@ -56,3 +89,57 @@ pub fn verify<RC: CircuitCoreDef, SC: CircuitCoreDef>(
 ) -> Result<(), VerificationError> {
     proof.verify(ctx, vk.0, pubs.digest())
 }
+
+/// Verifies a batch of independent proofs within the same `VerifierContext`.
+///
+/// Every item is a `(proof, image_id, pubs)` tuple, where `pubs` is the digest of the journal: the
+/// items are checked like [`Proof::verify`] does and a failure doesn't stop the verification of the
+/// following ones. The returned results are in the same order as `items`.
+pub fn verify_batch<RC: CircuitCoreDef, SC: CircuitCoreDef>(
+    ctx: &VerifierContext<RC, SC>,
+    items: &[(Proof, Digest, Digest)],
+) -> alloc::vec::Vec<Result<(), VerificationError>> {
+    let mut results = alloc::vec::Vec::with_capacity(items.len());
+    results.extend(
+        items
+            .iter()
+            .map(|(proof, image_id, pubs)| proof.verify(ctx, *image_id, *pubs)),
+    );
+    results
+}
+
+/// What [`verify_stream`] does after a failed verification.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StopPolicy {
+    /// Stop at the first failure, which is the last result.
+    FailFast,
+    /// Verify all the proofs, whatever the results.
+    ContinueAll,
+}
+
+/// Verifies a stream of independent proofs within the same `VerifierContext`, lazily: the streaming
+/// counterpart of [`verify_batch`], e.g. to verify the proofs received from a channel.
+///
+/// Every item is a `(proof, image_id, pubs)` tuple checked like [`Proof::verify`] does, when the
+/// next result is requested. With [`StopPolicy::FailFast`] no item is pulled after a failure.
+pub fn verify_stream<'a, RC: CircuitCoreDef, SC: CircuitCoreDef, I>(
+    ctx: &'a VerifierContext<RC, SC>,
+    items: I,
+    policy: StopPolicy,
+) -> impl Iterator<Item = Result<(), VerificationError>> + 'a
+where
+    I: IntoIterator<Item = (Proof, Digest, Digest)>,
+    I::IntoIter: 'a,
+{
+    let mut failed = false;
+    let mut items = items.into_iter();
+    core::iter::from_fn(move || {
+        if failed {
+            return None;
+        }
+        let (proof, image_id, pubs) = items.next()?;
+        let result = proof.verify(ctx, image_id, pubs);
+        failed = policy == StopPolicy::FailFast && result.is_err();
+        Some(result)
+    })
+}
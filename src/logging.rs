@@ -0,0 +1,72 @@
+// Copyright Copyright 2024, Horizen Labs, Inc.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Crate internal diagnostic messages.
+//!
+//! The `debug!` and `warn!` macros route the messages through `tracing` if the `trace` feature is enabled,
+//! otherwise through `log` if the `logging` feature (on by default) is enabled. Without both
+//! features the messages are compiled out and the verifier doesn't depend on any logging crate.
+
+#[cfg(feature = "trace")]
+macro_rules! debug {
+    ($($arg:tt)+) => {
+        tracing::debug!($($arg)+)
+    };
+}
+
+#[cfg(all(feature = "logging", not(feature = "trace")))]
+macro_rules! debug {
+    ($($arg:tt)+) => {
+        log::debug!($($arg)+)
+    };
+}
+
+#[cfg(not(any(feature = "logging", feature = "trace")))]
+macro_rules! debug {
+    ($($arg:tt)+) => {
+        // Never evaluated: just keep the arguments used.
+        if false {
+            let _ = format_args!($($arg)+);
+        }
+    };
+}
+
+#[cfg(feature = "trace")]
+#[allow(unused_macros)]
+macro_rules! warn {
+    ($($arg:tt)+) => {
+        tracing::warn!($($arg)+)
+    };
+}
+
+#[cfg(all(feature = "logging", not(feature = "trace")))]
+#[allow(unused_macros)]
+macro_rules! warn {
+    ($($arg:tt)+) => {
+        log::warn!($($arg)+)
+    };
+}
+
+#[cfg(not(any(feature = "logging", feature = "trace")))]
+#[allow(unused_macros)]
+macro_rules! warn {
+    ($($arg:tt)+) => {
+        if false {
+            let _ = format_args!($($arg)+);
+        }
+    };
+}
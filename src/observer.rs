@@ -0,0 +1,158 @@
+// Copyright Copyright 2024, Horizen Labs, Inc.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+use alloc::{rc::Rc, vec::Vec};
+use core::{cell::RefCell, fmt};
+
+use risc0_zkp::verify::VerificationError;
+use serde::{Deserialize, Serialize};
+
+/// Observer of the verification process, set through
+/// [`VerifierContext::with_observer()`][crate::VerifierContext::with_observer].
+///
+/// The verifier is `no_std` and has no clock: to collect timings the observer should take the
+/// time on the `*_start` calls and compute the elapsed time on the matching `*_end` ones. All the
+/// methods do nothing by default.
+pub trait VerifyObserver {
+    /// Called before verifying the segment at index `idx` of a
+    /// [CompositeReceipt][crate::CompositeReceipt], which has size `po2` as declared in its seal.
+    fn on_segment_start(&self, _idx: usize, _po2: u32) {}
+
+    /// Called after verifying the segment at index `idx`, with the verification `result`.
+    fn on_segment_end(&self, _idx: usize, _result: &Result<(), VerificationError>) {}
+
+    /// Called before verifying a [SuccinctReceipt][crate::SuccinctReceipt] proof.
+    fn on_succinct_start(&self) {}
+
+    /// Called after verifying a [SuccinctReceipt][crate::SuccinctReceipt] proof, with the
+    /// verification `result`.
+    fn on_succinct_end(&self, _result: &Result<(), VerificationError>) {}
+
+    /// Called after the `check` of the receipt as a whole, with its `result`.
+    fn on_check(&self, _check: VerifyCheck, _result: &Result<(), VerificationError>) {}
+}
+
+/// A check performed by the verification, reported to [`VerifyObserver::on_check`] and recorded
+/// by [`Proof::verify_with_trace`][crate::Proof::verify_with_trace].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub enum VerifyCheck {
+    /// The control ID of a seal is allowed by the context.
+    ControlId,
+    /// The seal of the segment at the given index of a [CompositeReceipt][crate::CompositeReceipt].
+    SegmentSeal(usize),
+    /// The seal of a [SuccinctReceipt][crate::SuccinctReceipt].
+    SuccinctSeal,
+    /// The seal of a `Groth16Receipt`.
+    Groth16Seal,
+    /// Every segment starts from the state where the previous one stopped.
+    Continuation,
+    /// The assumptions of a [CompositeReceipt][crate::CompositeReceipt] are proven by its
+    /// assumption receipts.
+    Assumptions,
+    /// The claim is not pruned, checked only by a context with
+    /// [`VerifierContext::strict_claims`][crate::VerifierContext::strict_claims].
+    StrictClaims,
+    /// The claim matches the expected image ID and journal digest.
+    ClaimDigest,
+}
+
+impl fmt::Display for VerifyCheck {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::ControlId => write!(f, "control id"),
+            Self::SegmentSeal(idx) => write!(f, "segment {idx} seal"),
+            Self::SuccinctSeal => write!(f, "succinct seal"),
+            Self::Groth16Seal => write!(f, "groth16 seal"),
+            Self::Continuation => write!(f, "continuation"),
+            Self::Assumptions => write!(f, "assumptions"),
+            Self::StrictClaims => write!(f, "strict claims"),
+            Self::ClaimDigest => write!(f, "claim digest"),
+        }
+    }
+}
+
+/// A [VerifyCheck] performed by the verification and whether it passed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VerifyStep {
+    /// The check.
+    pub check: VerifyCheck,
+    /// Whether the check passed.
+    pub passed: bool,
+}
+
+/// The observer that records the [VerifyStep]s, forwarding every notification to the observer of
+/// the context, if any.
+pub(crate) struct Trace {
+    inner: Option<Rc<dyn VerifyObserver>>,
+    steps: RefCell<Vec<VerifyStep>>,
+}
+
+impl Trace {
+    pub(crate) fn new(inner: Option<Rc<dyn VerifyObserver>>) -> Self {
+        Self {
+            inner,
+            steps: RefCell::default(),
+        }
+    }
+
+    pub(crate) fn take_steps(&self) -> Vec<VerifyStep> {
+        self.steps.take()
+    }
+
+    fn record(&self, check: VerifyCheck, result: &Result<(), VerificationError>) {
+        self.steps.borrow_mut().push(VerifyStep {
+            check,
+            passed: result.is_ok(),
+        });
+    }
+}
+
+impl VerifyObserver for Trace {
+    fn on_segment_start(&self, idx: usize, po2: u32) {
+        if let Some(inner) = &self.inner {
+            inner.on_segment_start(idx, po2);
+        }
+    }
+
+    fn on_segment_end(&self, idx: usize, result: &Result<(), VerificationError>) {
+        self.record(VerifyCheck::SegmentSeal(idx), result);
+        if let Some(inner) = &self.inner {
+            inner.on_segment_end(idx, result);
+        }
+    }
+
+    fn on_succinct_start(&self) {
+        if let Some(inner) = &self.inner {
+            inner.on_succinct_start();
+        }
+    }
+
+    fn on_succinct_end(&self, result: &Result<(), VerificationError>) {
+        self.record(VerifyCheck::SuccinctSeal, result);
+        if let Some(inner) = &self.inner {
+            inner.on_succinct_end(result);
+        }
+    }
+
+    fn on_check(&self, check: VerifyCheck, result: &Result<(), VerificationError>) {
+        self.record(check, result);
+        if let Some(inner) = &self.inner {
+            inner.on_check(check, result);
+        }
+    }
+}
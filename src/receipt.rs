@@ -16,20 +16,30 @@
 // limitations under the License.
 //
 
-use alloc::vec::Vec;
+use alloc::{rc::Rc, vec::Vec};
 use composite::CompositeReceipt;
+use risc0_binfmt::ExitCode;
 use risc0_zkp::{core::digest::Digest, verify::VerificationError};
-use serde::{Deserialize, Serialize};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 
+#[cfg(any(feature = "bincode", feature = "protobuf"))]
+use crate::ser::SerError;
 use crate::{
     circuit::CircuitCoreDef,
-    receipt_claim::{MaybePruned, ReceiptClaim, Unknown},
-    sha::{Digestible, Sha256},
-    VerifierContext,
+    error::Error,
+    journal::{JournalDecodeError, JournalEncodeError},
+    observer::{Trace, VerifyCheck, VerifyStep},
+    receipt_claim::{Assumptions, ClaimDiff, MaybePruned, Mismatch, ReceiptClaim, Unknown},
+    sha::{self, Digestible, Sha256},
+    VerifierContext, VerifierVersion,
 };
 use succinct::SuccinctReceipt;
 
 pub mod composite;
+#[cfg(feature = "unsafe-fake")]
+pub mod fake;
+#[cfg(feature = "groth16")]
+pub mod groth16;
 pub mod succinct;
 
 pub mod merkle;
@@ -39,9 +49,62 @@ pub mod merkle;
 /// a po2 higher than 21 shows a degradation of 1 bit of security per po2, to 94 bits at po2 24.
 pub const DEFAULT_MAX_PO2: usize = 21;
 
+/// The estimated bits of security when accepting segments up to `max_po2`, following the analysis
+/// of [DEFAULT_MAX_PO2]: 97 bits up to po2 21, then 1 bit less per po2. The po2 is clamped to the
+/// supported range, so it never goes below the 94 bits of po2 24. Use
+/// `security_bits(ctx.max_po2)` to check a [VerifierContext] against a policy threshold.
+pub const fn security_bits(max_po2: usize) -> u32 {
+    let po2 = if max_po2 < DEFAULT_MAX_PO2 {
+        DEFAULT_MAX_PO2
+    } else if max_po2 > risc0_zkp::MAX_CYCLES_PO2 {
+        risc0_zkp::MAX_CYCLES_PO2
+    } else {
+        max_po2
+    };
+    97 - (po2 - DEFAULT_MAX_PO2) as u32
+}
+
+/// The kind of an [InnerReceipt], i.e. the proof system used to generate it.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum ReceiptKind {
+    /// A [CompositeReceipt].
+    Composite,
+    /// A [SuccinctReceipt].
+    Succinct,
+    /// A Groth16 receipt: it can be verified only if the `groth16` feature is enabled.
+    Groth16,
+    /// A [FakeReceipt](fake::FakeReceipt), that proves nothing.
+    #[cfg(feature = "unsafe-fake")]
+    Fake,
+}
+
+impl ReceiptKind {
+    /// The lowercase name of this kind, handy to tag logs and metrics.
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Self::Composite => "composite",
+            Self::Succinct => "succinct",
+            Self::Groth16 => "groth16",
+            #[cfg(feature = "unsafe-fake")]
+            Self::Fake => "fake",
+        }
+    }
+}
+
+impl core::fmt::Display for ReceiptKind {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
 /// A wrapper around [InnerReceipt]. It can be deserialized from a Risc0 receipt where it
 /// just ignore the journal and metadata fields.
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "borsh",
+    derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
 pub struct Proof {
     /// The polymorphic [InnerReceipt].
     pub inner: InnerReceipt,
@@ -61,6 +124,18 @@ impl Proof {
     /// - The image ID matches the expected value.
     /// - The journal has not been tampered with.
     ///
+    /// If the claim is not pruned and it differs only for the exit code, it fails with
+    /// [`VerificationError::UnexpectedExitCode`] instead of a claim digest mismatch.
+    ///
+    /// A segment bigger than the `max_po2` of the context is rejected before verifying any seal,
+    /// but with the generic [`VerificationError::ControlVerificationError`]: the entry points that
+    /// return an [`Error`], e.g. [`Proof::verify_and_extract`], report it as
+    /// [`Error::SegmentPo2TooLarge`].
+    ///
+    /// The receipts of a proof always carry a [`ReceiptClaim`]: an assumption receipt, whose claim
+    /// type is `Unknown`, must be converted with [`InnerAssumptionReceipt::try_into_receipt`]
+    /// first, and then its pruned claim is checked by digest like any other.
+    ///
     /// Parameters:
     /// - `ctx`: The verification context that identifies the prover version used to generate the proof.
     ///   Refer to [VerifierContext] for more details.
@@ -72,40 +147,784 @@ impl Proof {
         image_id: impl Into<Digest>,
         pubs: impl Into<Digest>,
     ) -> Result<(), VerificationError> {
-        log::debug!("Receipt::verify_with_context");
+        debug!("Receipt::verify_with_context");
+        self.verify_claim(ctx, image_id, pubs).map(|_| ())
+    }
+
+    /// Verifies the proof like [`Proof::verify`], returning its result along with the
+    /// [VerifyStep]s of the checks performed, in order, e.g. for an audit trail. The verification
+    /// stops at the first failure, which is not a step if it isn't a check, e.g. a malformed seal.
+    ///
+    /// The context observer, if any, is still notified.
+    pub fn verify_with_trace<SC: CircuitCoreDef, RC: CircuitCoreDef>(
+        &self,
+        ctx: &VerifierContext<SC, RC>,
+        image_id: impl Into<Digest>,
+        pubs: impl Into<Digest>,
+    ) -> (Result<(), VerificationError>, Vec<VerifyStep>) {
+        debug!("Receipt::verify_with_trace");
+        let trace = Rc::new(Trace::new(ctx.observer.clone()));
+        let result = self.verify(&ctx.observed_by(trace.clone()), image_id, pubs);
+        (result, trace.take_steps())
+    }
+
+    /// Verifies the proof like [`Proof::verify`], but hashing the `journal` internally with the
+    /// [journal digest](Journal#impl-Digestible-for-Journal).
+    ///
+    /// Fails with [`VerificationError::ReceiptFormatError`], before hashing, if the journal is
+    /// longer than [`VerifierContext::max_journal_len`].
+    pub fn verify_with_journal<SC: CircuitCoreDef, RC: CircuitCoreDef>(
+        &self,
+        ctx: &VerifierContext<SC, RC>,
+        image_id: impl Into<Digest>,
+        journal: &Journal,
+    ) -> Result<(), VerificationError> {
+        debug!("Receipt::verify_with_journal");
+        self.verify_with_expected_journal(ctx, image_id, &journal.bytes)
+    }
+
+    /// Verifies the proof like [`Proof::verify_with_journal`], but against the raw `expected`
+    /// journal bytes: it succeeds only if the proof committed exactly these bytes.
+    pub fn verify_with_expected_journal<SC: CircuitCoreDef, RC: CircuitCoreDef>(
+        &self,
+        ctx: &VerifierContext<SC, RC>,
+        image_id: impl Into<Digest>,
+        expected: &[u8],
+    ) -> Result<(), VerificationError> {
+        debug!("Receipt::verify_with_expected_journal");
+        if let Some(max) = ctx.max_journal_len.filter(|&max| expected.len() > max) {
+            debug!("journal of {} bytes exceeds the max {max}", expected.len());
+            return Err(VerificationError::ReceiptFormatError);
+        }
+        self.verify(ctx, image_id, *sha::Impl::hash_bytes(expected))
+    }
+
+    /// Verifies the proof like [`Proof::verify`] and returns the [VerifiedFacts] it attests to,
+    /// ready to be persisted or forwarded.
+    pub fn verify_to_facts<SC: CircuitCoreDef, RC: CircuitCoreDef>(
+        &self,
+        ctx: &VerifierContext<SC, RC>,
+        image_id: impl Into<Digest>,
+        pubs: impl Into<Digest>,
+    ) -> Result<VerifiedFacts, VerificationError> {
+        debug!("Receipt::verify_to_facts");
+        let (image_id, journal_digest) = (image_id.into(), pubs.into());
+        self.verify(ctx, image_id, journal_digest)?;
+        Ok(VerifiedFacts {
+            image_id,
+            journal_digest,
+            exit_code: ExitCode::Halted(0),
+            verifier_parameters: self.verifier_parameters(),
+        })
+    }
+
+    /// Verifies the proof like [`Proof::verify`] and returns the verified [`ReceiptClaim`].
+    ///
+    /// If the receipt carries just the digest of the claim [`Error::PrunedClaim`] is returned even
+    /// if the proof is valid. A claim that differs only for the exit code fails with
+    /// [`Error::UnexpectedExitCode`], e.g. the receipt of a continuation that hasn't terminated
+    /// yet, with a `SystemSplit` exit code: accept it with [`Proof::verify_with_exit`].
+    pub fn verify_and_extract<SC: CircuitCoreDef, RC: CircuitCoreDef>(
+        &self,
+        ctx: &VerifierContext<SC, RC>,
+        image_id: impl Into<Digest>,
+        pubs: impl Into<Digest>,
+    ) -> Result<ReceiptClaim, Error> {
+        debug!("Receipt::verify_and_extract");
+        self.check_po2(ctx)?;
+        let (image_id, pubs) = (image_id.into(), pubs.into());
+        self.verify_claim(ctx, image_id, pubs)
+            .map_err(|e| self.detail_exit_code(e, image_id, pubs))?
+            .value()
+            .map_err(|e| Error::PrunedClaim(e.0))
+    }
+
+    /// Verifies the integrity of the proof and checks that it proves the claim with the given
+    /// digest: a lower level entry point to check any claim, e.g. one built with
+    /// [`ReceiptClaim::builder`], or a claim digest committed in advance (e.g. on chain) that
+    /// doesn't have the [`ReceiptClaim::ok`] shape.
+    pub fn verify_claim_digest<SC: CircuitCoreDef, RC: CircuitCoreDef>(
+        &self,
+        ctx: &VerifierContext<SC, RC>,
+        claim_digest: impl Into<Digest>,
+    ) -> Result<(), VerificationError> {
+        debug!("Receipt::verify_claim_digest");
+        self.inner.verify_integrity_with_context(ctx)?;
+
+        let (expected, received) = (claim_digest.into(), self.inner.claim()?.digest());
+        if !sha::digest_eq(&expected, &received) {
+            debug!("receipt claim {received} does not match the expected {expected}");
+            return Err(VerificationError::ClaimDigestMismatch { expected, received });
+        }
+        Ok(())
+    }
+
+    /// Verifies the proof against a set of acceptable image IDs and returns the one that matches.
+    ///
+    /// The seal is verified just once, then the claim is checked against every candidate in order
+    /// till the first match. Fails with [`Error::EmptyImageIds`] if `image_ids` is empty, with
+    /// [`Error::UnexpectedExitCode`] if the claim of an image ID differs only for the exit code,
+    /// and with [`VerificationError::ImageVerificationError`] if no image ID matches.
+    pub fn verify_any<SC: CircuitCoreDef, RC: CircuitCoreDef>(
+        &self,
+        ctx: &VerifierContext<SC, RC>,
+        image_ids: &[Digest],
+        pubs: impl Into<Digest>,
+    ) -> Result<Digest, Error> {
+        debug!("Receipt::verify_any");
+        if image_ids.is_empty() {
+            return Err(Error::EmptyImageIds);
+        }
+        self.check_po2(ctx)?;
+        self.inner.verify_integrity_with_context(ctx)?;
+
+        let pubs = pubs.into();
+        let claim = self.inner.claim()?;
+        let claim_digest = claim.digest();
+        image_ids
+            .iter()
+            .find(|&&image_id| {
+                sha::digest_eq(
+                    &ReceiptClaim::ok(image_id, MaybePruned::Pruned(pubs)).digest(),
+                    &claim_digest,
+                )
+            })
+            .copied()
+            .ok_or_else(|| {
+                debug!("receipt claim {claim_digest} does not match any of the image ids");
+                image_ids
+                    .iter()
+                    .find_map(|&image_id| mismatched_exit_code(&claim, image_id, pubs))
+                    .map_or(VerificationError::ImageVerificationError.into(), |found| {
+                        Error::UnexpectedExitCode {
+                            expected: ExitCode::Halted(0),
+                            found,
+                        }
+                    })
+            })
+    }
+
+    /// Verifies the proof like [`Proof::verify`], but accepting any of the given exit codes instead
+    /// of just `Halted(0)`, e.g. `Paused(0)` for continuation workflows. The image ID and the
+    /// journal are still enforced. Returns the exit code of the verified execution.
+    ///
+    /// A `SystemSplit` execution, i.e. a continuation that hasn't terminated yet, has not committed
+    /// any journal: if accepted, it's checked only against the image ID and `pubs` is ignored.
+    ///
+    /// Fails with [`VerificationError::UnexpectedExitCode`] if the exit code is not accepted.
+    pub fn verify_with_exit<SC: CircuitCoreDef, RC: CircuitCoreDef>(
+        &self,
+        ctx: &VerifierContext<SC, RC>,
+        image_id: impl Into<Digest>,
+        pubs: impl Into<Digest>,
+        accepted: &[ExitCode],
+    ) -> Result<ExitCode, VerificationError> {
+        debug!("Receipt::verify_with_exit");
+        self.inner.verify_integrity_with_context(ctx)?;
+
+        let (image_id, pubs) = (image_id.into(), pubs.into());
+        let claim = self.inner.claim()?;
+        let expected_claim = |exit_code| expected_claim(image_id, pubs, exit_code, &claim).digest();
+        let claim_digest = claim.digest();
+        if let Some(exit_code) = accepted
+            .iter()
+            .copied()
+            .find(|&exit_code| sha::digest_eq(&expected_claim(exit_code), &claim_digest))
+        {
+            return Ok(exit_code);
+        }
+
+        let exit_code = match claim.as_value() {
+            Ok(claim) if accepted.contains(&claim.exit_code) => claim.exit_code,
+            Ok(claim) => {
+                debug!("exit code {:?} is not accepted", claim.exit_code);
+                return Err(VerificationError::UnexpectedExitCode);
+            }
+            Err(_) => *accepted
+                .first()
+                .ok_or(VerificationError::UnexpectedExitCode)?,
+        };
+        Err(VerificationError::ClaimDigestMismatch {
+            expected: expected_claim(exit_code),
+            received: claim_digest,
+        })
+    }
+
+    /// The [ReceiptKind] of the inner receipt.
+    pub fn kind(&self) -> ReceiptKind {
+        self.inner.kind()
+    }
+
+    /// Returns `true` if the inner receipt is a [CompositeReceipt].
+    pub fn is_composite(&self) -> bool {
+        self.kind() == ReceiptKind::Composite
+    }
+
+    /// Returns `true` if the inner receipt is a [SuccinctReceipt].
+    pub fn is_succinct(&self) -> bool {
+        self.kind() == ReceiptKind::Succinct
+    }
+
+    /// Returns `true` if the proof should be compressed before being sent downstream, i.e. if it
+    /// is a [CompositeReceipt] and not already succinct.
+    pub fn needs_compression(&self) -> bool {
+        self.is_composite()
+    }
+
+    /// The [ProofHeader] of the proof: its metadata without the seals. The segment sizes are read
+    /// from the seal headers, whose layout all the supported versions share.
+    pub fn header(&self) -> ProofHeader {
+        let (segment_count, total_cycles) = match &self.inner {
+            InnerReceipt::Composite(inner) => (
+                inner.segment_count(),
+                inner
+                    .total_cycles::<crate::circuit::SharedSegmentLayout>()
+                    .ok(),
+            ),
+            _ => (0, None),
+        };
+        ProofHeader {
+            kind: self.kind(),
+            segment_count,
+            total_cycles,
+            verifier_parameters: self.verifier_parameters(),
+            claim_digest: self.inner.claim().ok().map(|claim| claim.digest()),
+        }
+    }
+
+    /// Check that the proof makes no assumptions, e.g. right after [`Proof::verify`] to reject the
+    /// composed proofs: a [CompositeReceipt] must carry no assumption receipts, and the claim must
+    /// commit to an empty assumptions list.
+    ///
+    /// Fails with [`VerificationError::UnresolvedAssumption`] holding the digest of the first
+    /// assumption receipt or of the assumptions list, and with
+    /// [`VerificationError::ReceiptFormatError`] if the output of the claim is pruned.
+    pub fn require_no_assumptions(&self) -> Result<(), VerificationError> {
+        let claim = match &self.inner {
+            InnerReceipt::Composite(inner) => {
+                if let Some(receipt) = inner.assumption_receipts.first() {
+                    return Err(VerificationError::UnresolvedAssumption {
+                        digest: receipt.claim_digest()?,
+                    });
+                }
+                // The claim of the composite receipt drops the assumptions of its last segment.
+                &inner
+                    .segments
+                    .last()
+                    .ok_or(VerificationError::ReceiptFormatError)?
+                    .claim
+            }
+            inner => inner
+                .claim_ref()
+                .ok_or(VerificationError::ReceiptFormatError)?
+                .as_value()?,
+        };
+        match claim.assumptions_digest()? {
+            Some(digest) if digest != Assumptions(Vec::new()).digest() => {
+                Err(VerificationError::UnresolvedAssumption { digest })
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Return the digest of the verifier parameters declared by the inner receipt, i.e. the
+    /// parameter set it must be verified with.
+    pub fn verifier_parameters(&self) -> Digest {
+        self.inner.verifier_parameters()
+    }
+
+    /// Return the risc0 vm version that generated this proof, detected from the digest of its
+    /// verifier parameters. Returns `None` if the digest doesn't match any known version.
+    pub fn detect_version(&self) -> Option<VerifierVersion> {
+        VerifierVersion::for_parameters(self.verifier_parameters())
+    }
+
+    /// Verifies the proof like [`Proof::verify`], but using the built-in [VerifierContext] of the
+    /// version detected by [`Proof::detect_version`], that is returned on success.
+    ///
+    /// Fails with [`Error::UnknownVerifierParameters`] if the version cannot be detected, and with
+    /// [`Error::UnexpectedExitCode`] if the claim differs only for the exit code.
+    pub fn verify_auto(
+        &self,
+        image_id: impl Into<Digest>,
+        pubs: impl Into<Digest>,
+    ) -> Result<VerifierVersion, Error> {
+        debug!("Receipt::verify_auto");
+        let version = self
+            .detect_version()
+            .ok_or_else(|| Error::UnknownVerifierParameters(self.verifier_parameters()))?;
+        self.verify_with_version(version, image_id.into(), pubs.into())?;
+        Ok(version)
+    }
+
+    /// Verifies the proof like [`Proof::verify`] with every built-in [VerifierContext], from the
+    /// oldest to the newest, and returns the version of the only one that accepts it: a fallback
+    /// to [`Proof::verify_auto`] when the verifier parameters digest is unknown, e.g. for a receipt
+    /// re-serialized without it. It costs one verification per version.
+    ///
+    /// A context accepts the proof when it accepts the seals, even if the claim then doesn't
+    /// match. With the `std` feature a panic of the risc0 verifier, e.g. checking a `1.1` SHA-256
+    /// segment against the `1.2` circuit that shares its control IDs, counts as a rejection;
+    /// without it the panic is not caught.
+    ///
+    /// Fails with [`Error::AmbiguousVersion`] if more than one context accepts the seals, with the
+    /// error of the context that accepted them, e.g. [`Error::UnexpectedExitCode`] like
+    /// [`Proof::verify_auto`], or with the error of the newest context if none did.
+    pub fn verify_trying_all(
+        &self,
+        image_id: impl Into<Digest>,
+        pubs: impl Into<Digest>,
+    ) -> Result<VerifierVersion, Error> {
+        debug!("Receipt::verify_trying_all");
+        let (image_id, pubs) = (image_id.into(), pubs.into());
+        let mut accepted = Vec::new();
+        let mut outcome = Err(Error::UnknownVerifierParameters(self.verifier_parameters()));
+        for version in VerifierVersion::ALL {
+            match self.try_version(version, image_id, pubs) {
+                Err(e) if rejects_the_seal(&e) => {
+                    debug!("the proof doesn't verify with {version}: {e:?}");
+                    if accepted.is_empty() {
+                        outcome = Err(e);
+                    }
+                }
+                result => {
+                    accepted.push(version);
+                    outcome = result.map(|()| version);
+                }
+            }
+        }
+        if accepted.len() > 1 {
+            return Err(Error::AmbiguousVersion(accepted));
+        }
+        outcome
+    }
+
+    /// [`Proof::verify_with_version`] that reports a panic of the risc0 verifier as an
+    /// [`VerificationError::InvalidProof`], when the `std` feature can catch it.
+    fn try_version(
+        &self,
+        version: VerifierVersion,
+        image_id: Digest,
+        pubs: Digest,
+    ) -> Result<(), Error> {
+        #[cfg(feature = "std")]
+        {
+            std::panic::catch_unwind(core::panic::AssertUnwindSafe(|| {
+                self.verify_with_version(version, image_id, pubs)
+            }))
+            .unwrap_or_else(|_| {
+                debug!("the proof verification panicked with {version}");
+                Err(VerificationError::InvalidProof.into())
+            })
+        }
+        #[cfg(not(feature = "std"))]
+        self.verify_with_version(version, image_id, pubs)
+    }
+
+    /// [`Proof::verify_checked`] with the built-in [VerifierContext] of `version`.
+    fn verify_with_version(
+        &self,
+        version: VerifierVersion,
+        image_id: Digest,
+        pubs: Digest,
+    ) -> Result<(), Error> {
+        match version {
+            VerifierVersion::V1_0 => self.verify_checked(&VerifierContext::v1_0(), image_id, pubs),
+            VerifierVersion::V1_1 => self.verify_checked(&VerifierContext::v1_1(), image_id, pubs),
+            VerifierVersion::V1_2 => self.verify_checked(&VerifierContext::v1_2(), image_id, pubs),
+        }
+    }
+
+    /// [`Proof::verify`] preceded by [`Proof::check_po2`].
+    fn verify_checked<SC: CircuitCoreDef, RC: CircuitCoreDef>(
+        &self,
+        ctx: &VerifierContext<SC, RC>,
+        image_id: Digest,
+        pubs: Digest,
+    ) -> Result<(), Error> {
+        self.check_po2(ctx)?;
+        self.verify(ctx, image_id, pubs)
+            .map_err(|e| self.detail_exit_code(e, image_id, pubs))
+    }
+
+    /// Convert the error of a verification against `image_id` and `pubs`, reporting a
+    /// [`VerificationError::UnexpectedExitCode`] as [`Error::UnexpectedExitCode`] with the exit
+    /// code of the claim.
+    fn detail_exit_code(&self, e: VerificationError, image_id: Digest, pubs: Digest) -> Error {
+        match e {
+            VerificationError::UnexpectedExitCode => self
+                .claim()
+                .ok()
+                .and_then(|claim| mismatched_exit_code(&claim, image_id, pubs))
+                .map_or(e.into(), |found| Error::UnexpectedExitCode {
+                    expected: ExitCode::Halted(0),
+                    found,
+                }),
+            e => e.into(),
+        }
+    }
+
+    fn verify_claim<SC: CircuitCoreDef, RC: CircuitCoreDef>(
+        &self,
+        ctx: &VerifierContext<SC, RC>,
+        image_id: impl Into<Digest>,
+        pubs: impl Into<Digest>,
+    ) -> Result<MaybePruned<ReceiptClaim>, VerificationError> {
         self.inner.verify_integrity_with_context(ctx)?;
 
         // Check that the claim on the verified receipt matches what was expected. Since we have
         // constrained all field in the ReceiptClaim, we can directly construct the expected digest
         // and do not need to open the claim digest on the inner receipt.
-        let expected_claim = ReceiptClaim::ok(image_id, MaybePruned::Pruned(pubs.into()));
-        if expected_claim.digest() != self.inner.claim()?.digest() {
-            log::debug!(
+        let (image_id, pubs) = (image_id.into(), pubs.into());
+        let expected_claim = ReceiptClaim::ok(image_id, MaybePruned::Pruned(pubs));
+        let claim = self.inner.claim()?;
+        let result = if sha::digest_eq(&expected_claim.digest(), &claim.digest()) {
+            Ok(())
+        } else {
+            debug!(
                 "receipt claim does not match expected claim:\nreceipt: {:#?}\nexpected: {:#?}",
-                self.inner.claim()?,
-                expected_claim
+                claim, expected_claim
             );
-            return Err(VerificationError::ClaimDigestMismatch {
-                expected: expected_claim.digest(),
-                received: self.claim()?.digest(),
-            });
+            if let Some(found) = mismatched_exit_code(&claim, image_id, pubs) {
+                debug!("the claim differs just for the exit code {found:?}");
+                Err(VerificationError::UnexpectedExitCode)
+            } else {
+                Err(VerificationError::ClaimDigestMismatch {
+                    expected: expected_claim.digest(),
+                    received: claim.digest(),
+                })
+            }
+        };
+        ctx.observe(VerifyCheck::ClaimDigest, result)?;
+
+        Ok(claim)
+    }
+
+    /// Explain why the claim of this proof doesn't match the one expected for `image_id` and
+    /// `pubs`, e.g. after a [`VerificationError::ClaimDigestMismatch`]: the returned [ClaimDiff]
+    /// is empty if they match. Only the claim digest can be compared if the claim is pruned.
+    ///
+    /// This doesn't verify the seal.
+    pub fn explain_mismatch(
+        &self,
+        image_id: impl Into<Digest>,
+        pubs: impl Into<Digest>,
+    ) -> Result<ClaimDiff, VerificationError> {
+        let expected = ReceiptClaim::ok(image_id, MaybePruned::Pruned(pubs.into()));
+        Ok(match self.claim()? {
+            MaybePruned::Value(claim) => claim.diff(&expected),
+            MaybePruned::Pruned(found) => ClaimDiff {
+                claim: (found != expected.digest()).then_some(Mismatch {
+                    expected: expected.digest(),
+                    found,
+                }),
+                ..Default::default()
+            },
+        })
+    }
+
+    /// Verifies just the integrity of the proof, i.e. that its claim is attested to by the seal,
+    /// without matching the claim against any image ID or journal: inspect it with
+    /// [`Proof::claim`] afterward.
+    pub fn verify_integrity<SC: CircuitCoreDef, RC: CircuitCoreDef>(
+        &self,
+        ctx: &VerifierContext<SC, RC>,
+    ) -> Result<(), VerificationError> {
+        debug!("Receipt::verify_integrity");
+        self.inner.verify_integrity_with_context(ctx)
+    }
+
+    /// Check that a composite proof doesn't exceed the segment and the assumption limits of the
+    /// context, without verifying the seals: see [`CompositeReceipt::check_limits`]. The other
+    /// receipt kinds always pass. The verification runs this check too, but reports a failure as
+    /// [`VerificationError::ReceiptFormatError`].
+    pub fn check_limits<SC: CircuitCoreDef, RC: CircuitCoreDef>(
+        &self,
+        ctx: &VerifierContext<SC, RC>,
+    ) -> Result<(), Error> {
+        match &self.inner {
+            InnerReceipt::Composite(inner) => inner.check_limits(ctx),
+            _ => Ok(()),
         }
+    }
 
-        Ok(())
+    /// Check that the segments of a composite proof are not bigger than the `max_po2` of the
+    /// context, without verifying the seals: see [`CompositeReceipt::check_po2`]. The other
+    /// receipt kinds always pass.
+    ///
+    /// Every entry point that returns an [`Error`], e.g. [`Proof::verify_and_extract`],
+    /// [`Proof::verify_any`], [`Proof::verify_auto`] and [`Proof::verify_trying_all`], runs this
+    /// check before verifying the seals.
+    pub fn check_po2<SC: CircuitCoreDef, RC: CircuitCoreDef>(
+        &self,
+        ctx: &VerifierContext<SC, RC>,
+    ) -> Result<(), Error> {
+        match &self.inner {
+            InnerReceipt::Composite(inner) => inner.check_po2(ctx),
+            _ => Ok(()),
+        }
+    }
+
+    /// Check that the proof is structurally valid without any cryptographic verification: a
+    /// composite proof has at least one segment and every seal has a length consistent with its
+    /// circuit and declares a po2 in range. It's a cheap pre-filter against garbage: a proof that
+    /// passes it can still fail the verification.
+    pub fn validate_structure(&self) -> Result<(), VerificationError> {
+        self.inner.validate_structure()
     }
 
     /// Extract the [ReceiptClaim] from this receipt.
     pub fn claim(&self) -> Result<MaybePruned<ReceiptClaim>, VerificationError> {
         self.inner.claim()
     }
+
+    /// Read the [ReceiptKind] of an encoded proof without deserializing it: both the `bincode`
+    /// encoding of `Proof::to_bincode` and the versioned one of `Proof::to_bytes` are accepted,
+    /// the latter only with the supported [PROOF_FORMAT_VERSION]. Only the leading enum tag is
+    /// inspected, so the remaining bytes are not validated at all. The tag is the index of the
+    /// [InnerReceipt] variant among the ones enabled by the features, so the same bytes can peek
+    /// a different kind on another build.
+    ///
+    /// [PROOF_FORMAT_VERSION]: crate::PROOF_FORMAT_VERSION
+    pub fn peek_kind(bytes: &[u8]) -> Result<ReceiptKind, VerificationError> {
+        // Same order and gates as the `InnerReceipt` variants.
+        const KINDS: &[ReceiptKind] = &[
+            ReceiptKind::Composite,
+            ReceiptKind::Succinct,
+            #[cfg(feature = "groth16")]
+            ReceiptKind::Groth16,
+            #[cfg(feature = "unsafe-fake")]
+            ReceiptKind::Fake,
+        ];
+        // The magic is never a valid tag, so it cannot be confused with a bincode proof.
+        let bytes = match bytes.strip_prefix(&crate::PROOF_FORMAT_MAGIC) {
+            Some(versioned) => match versioned.split_first_chunk::<2>() {
+                Some((version, encoded))
+                    if u16::from_le_bytes(*version) == crate::PROOF_FORMAT_VERSION =>
+                {
+                    encoded
+                }
+                _ => return Err(VerificationError::ReceiptFormatError),
+            },
+            None => bytes,
+        };
+        let tag = bytes
+            .first_chunk::<4>()
+            .ok_or(VerificationError::ReceiptFormatError)?;
+        usize::try_from(u32::from_le_bytes(*tag))
+            .ok()
+            .and_then(|tag| KINDS.get(tag).copied())
+            .ok_or(VerificationError::ReceiptFormatError)
+    }
+
+    /// Serialize the proof with the pinned `bincode` configuration: fixed size integers in little
+    /// endian order (the same bytes produced by `bincode::serialize`).
+    #[cfg(feature = "bincode")]
+    pub fn to_bincode(&self) -> Result<Vec<u8>, SerError> {
+        crate::ser::to_bincode(self)
+    }
+
+    /// Deserialize a proof encoded with [`Proof::to_bincode`], or with the `bincode::serialize` of
+    /// `bincode` 1.x, e.g. by an older toolchain. Trailing bytes are rejected.
+    #[cfg(feature = "bincode")]
+    pub fn from_bincode(bytes: &[u8]) -> Result<Self, SerError> {
+        crate::ser::from_bincode(bytes)
+    }
+
+    /// Serialize the proof with the versioned encoding: the [PROOF_FORMAT_MAGIC] and the
+    /// [PROOF_FORMAT_VERSION] followed by the [`Proof::to_bincode`] bytes. Prefer it to store the
+    /// proofs that may be read by another version of the crate.
+    ///
+    /// [PROOF_FORMAT_MAGIC]: crate::PROOF_FORMAT_MAGIC
+    /// [PROOF_FORMAT_VERSION]: crate::PROOF_FORMAT_VERSION
+    #[cfg(feature = "bincode")]
+    pub fn to_bytes(&self) -> Result<Vec<u8>, SerError> {
+        crate::ser::to_versioned_bytes(self)
+    }
+
+    /// Deserialize a proof encoded with [`Proof::to_bytes`]. Fails with
+    /// [`SerError::MissingFormatHeader`] if the bytes don't start with the magic, e.g. for a plain
+    /// [`Proof::to_bincode`] encoding, and with [`SerError::UnsupportedProofFormatVersion`] if the
+    /// format version is not the supported one.
+    #[cfg(feature = "bincode")]
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, SerError> {
+        crate::ser::from_versioned_bytes(bytes)
+    }
+
+    /// Read a proof encoded with [`Proof::to_bincode`] from `reader`, enforcing the given
+    /// [Limits]: it fails with [`SerError::TooManySegments`] before reading the segments of a
+    /// composite proof with too many of them, and with [`SerError::TooLarge`] as soon as more than
+    /// `max_size` bytes are needed.
+    #[cfg(feature = "bincode")]
+    pub fn from_reader<R: std::io::Read>(
+        reader: R,
+        limits: &crate::Limits,
+    ) -> Result<Self, SerError> {
+        crate::ser::from_bincode_reader(reader, limits)
+    }
+
+    /// Encode the proof as the `Proof` protobuf message described by `proto/proof.proto`.
+    #[cfg(feature = "protobuf")]
+    pub fn to_proto_bytes(&self) -> Vec<u8> {
+        crate::ser::proto::to_proto(self)
+    }
+
+    /// Decode a proof encoded with [`Proof::to_proto_bytes`], or by any other protobuf
+    /// implementation of `proto/proof.proto`.
+    #[cfg(feature = "protobuf")]
+    pub fn from_proto_bytes(bytes: &[u8]) -> Result<Self, SerError> {
+        crate::ser::proto::from_proto(bytes)
+    }
+
+    /// A SHA-256 digest of the whole proof encoded by [`Proof::to_bincode`], usable as a content
+    /// address: the pinned encoding makes it stable across runs and platforms. Unlike the
+    /// [ReceiptClaim] digest, it changes with the seal.
+    #[cfg(feature = "bincode")]
+    pub fn content_digest(&self) -> Result<Digest, SerError> {
+        Ok(*sha::Impl::hash_bytes(&self.to_bincode()?))
+    }
+
+    /// Deserialize a proof from a risc0 `Receipt` exported as JSON: the `journal` and `metadata`
+    /// fields are ignored, so the journal should still be provided to the verification.
+    #[cfg(feature = "json")]
+    pub fn from_risc0_json(s: &str) -> Result<Self, VerificationError> {
+        serde_json::from_str(s).map_err(|_e| {
+            debug!("invalid risc0 json receipt: {_e}");
+            VerificationError::ReceiptFormatError
+        })
+    }
+}
+
+/// Decode a proof encoded with [`Proof::to_bincode`].
+#[cfg(feature = "bincode")]
+impl TryFrom<&[u8]> for Proof {
+    type Error = SerError;
+
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        Self::from_bincode(bytes)
+    }
+}
+
+/// Decode a proof encoded with [`Proof::to_bincode`].
+#[cfg(feature = "bincode")]
+impl TryFrom<Vec<u8>> for Proof {
+    type Error = SerError;
+
+    fn try_from(bytes: Vec<u8>) -> Result<Self, Self::Error> {
+        Self::from_bincode(&bytes)
+    }
+}
+
+/// Encode a proof with [`Proof::to_bincode`].
+#[cfg(feature = "bincode")]
+impl TryFrom<&Proof> for Vec<u8> {
+    type Error = SerError;
+
+    fn try_from(proof: &Proof) -> Result<Self, Self::Error> {
+        proof.to_bincode()
+    }
+}
+
+/// Whether the error comes from a seal that doesn't verify with the context, rather than from a
+/// verified seal whose claim doesn't match.
+fn rejects_the_seal(error: &Error) -> bool {
+    matches!(
+        error,
+        Error::Verification(
+            VerificationError::ReceiptFormatError
+                | VerificationError::ControlVerificationError { .. }
+                | VerificationError::MerkleQueryOutOfRange { .. }
+                | VerificationError::InvalidProof
+                | VerificationError::InvalidHashSuite
+                | VerificationError::VerifierParametersMissing
+                | VerificationError::ProofSystemInfoMismatch { .. }
+                | VerificationError::CircuitInfoMismatch { .. }
+        )
+    )
+}
+
+/// The claim of an execution of `image_id` that committed `pubs` and terminated with `exit_code`.
+/// A `SystemSplit` execution is not over: it has no output yet and its post state, taken from the
+/// received `claim`, is not constrained.
+fn expected_claim(
+    image_id: Digest,
+    pubs: Digest,
+    exit_code: ExitCode,
+    claim: &MaybePruned<ReceiptClaim>,
+) -> ReceiptClaim {
+    let ok = ReceiptClaim::ok(image_id, MaybePruned::Pruned(pubs));
+    match (exit_code, claim.as_value()) {
+        (ExitCode::SystemSplit, Ok(claim)) => ReceiptClaim {
+            post: claim.post.clone(),
+            exit_code,
+            output: None.into(),
+            ..ok
+        },
+        _ => ReceiptClaim { exit_code, ..ok },
+    }
+}
+
+/// Return the exit code of `claim` if it's different from `Halted(0)` and it's the only field that
+/// doesn't match the claim expected for `image_id` and `pubs`. A pruned claim can't be inspected.
+fn mismatched_exit_code(
+    claim: &MaybePruned<ReceiptClaim>,
+    image_id: Digest,
+    pubs: Digest,
+) -> Option<ExitCode> {
+    let exit_code = claim.as_value().ok()?.exit_code;
+    let expected = expected_claim(image_id, pubs, exit_code, claim);
+    (exit_code != ExitCode::Halted(0) && sha::digest_eq(&expected.digest(), &claim.digest()))
+        .then_some(exit_code)
 }
 
 /// A record of the public commitments from a proven zkVM execution.
 ///
+/// The facts attested by a proof verified with [`Proof::verify_to_facts`].
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub struct VerifiedFacts {
+    /// The image ID of the guest.
+    pub image_id: Digest,
+    /// The SHA-256 digest of the journal.
+    pub journal_digest: Digest,
+    /// The exit code of the execution: `Halted(0)`, the only one accepted by [`Proof::verify`].
+    pub exit_code: ExitCode,
+    /// The digest of the verifier parameters declared by the receipt, as returned by
+    /// [`Proof::verifier_parameters`]: it identifies the risc0 version of the proof.
+    pub verifier_parameters: Digest,
+}
+
+/// The metadata of a [`Proof`], returned by [`Proof::header`]: everything but the seals, e.g. to
+/// index the proofs and fetch the full one only to verify it.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub struct ProofHeader {
+    /// The kind of the inner receipt.
+    pub kind: ReceiptKind,
+    /// The number of segments of a [CompositeReceipt], 0 for the other kinds.
+    pub segment_count: usize,
+    /// The total number of cycles of a [CompositeReceipt], as returned by
+    /// [`CompositeReceipt::total_cycles`]: `None` for the other kinds, or if a segment doesn't
+    /// declare a valid po2.
+    pub total_cycles: Option<u64>,
+    /// The digest of the verifier parameters, as returned by [`Proof::verifier_parameters`].
+    pub verifier_parameters: Digest,
+    /// The digest of the receipt claim, or `None` if the claim of a [CompositeReceipt] cannot be
+    /// computed from its segments.
+    pub claim_digest: Option<Digest>,
+}
+
 /// Public outputs, including commitments to critical inputs, are written to the journal during
 /// zkVM execution. Together with an image ID, these form the statement proven by a given
 /// [`Proof`].
-#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq)]
+///
+/// The receipt claim commits to the [digest](#impl-Digestible-for-Journal) of the journal bytes,
+/// whatever they contain: a guest that commits a Keccak-256 hash, e.g. to match an EVM state,
+/// commits it as journal bytes. Verify the proof against the expected journal, e.g. with
+/// [`Proof::verify_with_expected_journal`], then compare the committed hash with the on-chain one.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "borsh",
+    derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
 pub struct Journal {
     /// The raw bytes of the journal.
     pub bytes: Vec<u8>,
@@ -116,9 +935,45 @@ impl Journal {
     pub fn new(bytes: Vec<u8>) -> Self {
         Self { bytes }
     }
+
+    /// Build the [Journal] where the guest committed `value`: [Journal::decode()] is the inverse
+    /// of this function. Refer to [crate::journal] for more details about the encoding.
+    pub fn encode<T: Serialize + ?Sized>(value: &T) -> Result<Self, JournalEncodeError> {
+        crate::journal::encode_to_vec(value).map(Self::new)
+    }
+
+    /// Decode the value of type `T` committed by the guest in this journal. Refer to
+    /// [crate::journal] for more details about the encoding.
+    pub fn decode<T: DeserializeOwned>(&self) -> Result<T, JournalDecodeError> {
+        crate::journal::decode_from_slice(&self.bytes)
+    }
+
+    /// Iterate over the little endian `u32` words of this journal, without decoding them. Fails
+    /// with [JournalDecodeError::TrailingBytes] if the length is not a multiple of 4 bytes.
+    pub fn words(&self) -> Result<impl Iterator<Item = u32> + '_, JournalDecodeError> {
+        let (words, []) = self.bytes.as_chunks::<4>() else {
+            return Err(JournalDecodeError::TrailingBytes);
+        };
+        Ok(words.iter().copied().map(u32::from_le_bytes))
+    }
+
+    /// Read the little endian `u32` word at `word_index`, e.g. a word of a committed hash. Returns
+    /// `None` if the index is out of range or the length is not a multiple of 4 bytes.
+    pub fn read_u32_at(&self, word_index: usize) -> Option<u32> {
+        self.words().ok()?.nth(word_index)
+    }
+
+    /// Returns `true` if `digest` is the [digest](#impl-Digestible-for-Journal) of this journal,
+    /// e.g. a cached one.
+    pub fn matches_digest(&self, digest: impl Into<Digest>) -> bool {
+        sha::digest_eq(&self.digest(), &digest.into())
+    }
 }
 
 impl risc0_binfmt::Digestible for Journal {
+    /// Hash the journal bytes. The risc0 claims are always hashed with SHA-256, whatever hash
+    /// function was used to build the seal (e.g. `poseidon2` or `blake2b`), so the journal digest
+    /// to verify against doesn't depend on the [VerifierContext] either.
     fn digest<S: Sha256>(&self) -> Digest {
         *S::hash_bytes(&self.bytes)
     }
@@ -133,13 +988,25 @@ impl AsRef<[u8]> for Journal {
 /// A lower level receipt, containing the cryptographic seal (i.e. zero-knowledge proof) and
 /// verification logic for a specific proof system and circuit. All inner receipt types are
 /// zero-knowledge proofs of execution for a RISC-V zkVM.
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "borsh",
+    derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
 #[allow(clippy::large_enum_variant)]
 pub enum InnerReceipt {
     /// A non-succinct [CompositeReceipt], made up of one inner receipt per segment.
     Composite(CompositeReceipt),
     /// A [SuccinctReceipt], proving arbitrarily long zkVM computations with a single STARK.
     Succinct(SuccinctReceipt<ReceiptClaim>),
+    /// A [Groth16Receipt](groth16::Groth16Receipt), proving arbitrarily long zkVM computations
+    /// with a single Groth16 SNARK.
+    #[cfg(feature = "groth16")]
+    Groth16(groth16::Groth16Receipt<ReceiptClaim>),
+    /// A [FakeReceipt](fake::FakeReceipt), that carries a claim without proving it. Available
+    /// only with the `unsafe-fake` feature, for tests.
+    #[cfg(feature = "unsafe-fake")]
+    Fake(fake::FakeReceipt<ReceiptClaim>),
 }
 
 impl InnerReceipt {
@@ -148,10 +1015,60 @@ impl InnerReceipt {
         &self,
         ctx: &VerifierContext<SC, RC>,
     ) -> Result<(), VerificationError> {
-        log::debug!("InnerReceipt::verify_integrity_with_context");
+        debug!("InnerReceipt::verify_integrity_with_context");
         match self {
             Self::Composite(inner) => inner.verify_integrity_with_context(ctx),
-            Self::Succinct(inner) => inner.verify_integrity_with_context(ctx),
+            Self::Succinct(inner) => match ctx.observer.as_ref() {
+                None => inner.verify_integrity_with_context(ctx),
+                Some(observer) => {
+                    observer.on_succinct_start();
+                    let result = inner.verify_integrity_with_context(ctx);
+                    observer.on_succinct_end(&result);
+                    result
+                }
+            },
+            #[cfg(feature = "groth16")]
+            Self::Groth16(inner) => ctx.observe(
+                VerifyCheck::Groth16Seal,
+                inner.verify_integrity_with_context(ctx),
+            ),
+            #[cfg(feature = "unsafe-fake")]
+            Self::Fake(inner) => inner.verify_integrity(),
+        }?;
+        if ctx.strict_claims {
+            let result = if self.claim()?.is_pruned() {
+                debug!("receipt claim is pruned but the context requires strict claims");
+                Err(VerificationError::ReceiptFormatError)
+            } else {
+                Ok(())
+            };
+            ctx.observe(VerifyCheck::StrictClaims, result)?;
+        }
+        Ok(())
+    }
+
+    /// Check the structure of this receipt without verifying it: refer to
+    /// [`Proof::validate_structure`].
+    pub fn validate_structure(&self) -> Result<(), VerificationError> {
+        match self {
+            Self::Composite(inner) => inner.validate_structure(),
+            Self::Succinct(inner) => inner.validate_structure(),
+            #[cfg(feature = "groth16")]
+            Self::Groth16(inner) => inner.validate_structure(),
+            #[cfg(feature = "unsafe-fake")]
+            Self::Fake(_) => Ok(()),
+        }
+    }
+
+    /// The [ReceiptKind] of this receipt.
+    pub fn kind(&self) -> ReceiptKind {
+        match self {
+            Self::Composite(_) => ReceiptKind::Composite,
+            Self::Succinct(_) => ReceiptKind::Succinct,
+            #[cfg(feature = "groth16")]
+            Self::Groth16(_) => ReceiptKind::Groth16,
+            #[cfg(feature = "unsafe-fake")]
+            Self::Fake(_) => ReceiptKind::Fake,
         }
     }
 
@@ -173,11 +1090,39 @@ impl InnerReceipt {
         }
     }
 
+    /// Returns the [`InnerReceipt::Groth16`] arm.
+    #[cfg(feature = "groth16")]
+    pub fn groth16(&self) -> Result<&groth16::Groth16Receipt<ReceiptClaim>, VerificationError> {
+        if let Self::Groth16(x) = self {
+            Ok(x)
+        } else {
+            Err(VerificationError::ReceiptFormatError)
+        }
+    }
+
     /// Extract the [`ReceiptClaim`] from this receipt.
     pub fn claim(&self) -> Result<MaybePruned<ReceiptClaim>, VerificationError> {
         match self {
             Self::Composite(ref inner) => Ok(inner.claim()?.into()),
             Self::Succinct(ref inner) => Ok(inner.claim.clone()),
+            #[cfg(feature = "groth16")]
+            Self::Groth16(ref inner) => Ok(inner.claim.clone()),
+            #[cfg(feature = "unsafe-fake")]
+            Self::Fake(ref inner) => Ok(inner.claim.clone()),
+        }
+    }
+
+    /// Borrow the [`ReceiptClaim`] stored in this receipt, without cloning it. Returns `None` for
+    /// a [`CompositeReceipt`], whose claim is computed from its segments by
+    /// [`InnerReceipt::claim`].
+    pub fn claim_ref(&self) -> Option<&MaybePruned<ReceiptClaim>> {
+        match self {
+            Self::Composite(_) => None,
+            Self::Succinct(ref inner) => Some(&inner.claim),
+            #[cfg(feature = "groth16")]
+            Self::Groth16(ref inner) => Some(&inner.claim),
+            #[cfg(feature = "unsafe-fake")]
+            Self::Fake(ref inner) => Some(&inner.claim),
         }
     }
 
@@ -186,6 +1131,11 @@ impl InnerReceipt {
         match self {
             Self::Composite(ref inner) => inner.verifier_parameters,
             Self::Succinct(ref inner) => inner.verifier_parameters,
+            #[cfg(feature = "groth16")]
+            Self::Groth16(ref inner) => inner.verifier_parameters,
+            // A fake receipt has no verifier.
+            #[cfg(feature = "unsafe-fake")]
+            Self::Fake(_) => Digest::ZERO,
         }
     }
 }
@@ -193,13 +1143,26 @@ impl InnerReceipt {
 /// An enumeration of receipt types similar to [`InnerReceipt`], but for use in [AssumptionReceipt].
 /// Instead of proving only RISC-V execution with [`ReceiptClaim`], this type can prove any claim
 /// implemented by one of its inner types.
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "borsh",
+    derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
 pub enum InnerAssumptionReceipt {
     /// A non-succinct [CompositeReceipt], made up of one inner receipt per segment and assumption.
     Composite(CompositeReceipt),
 
     /// A [SuccinctReceipt], proving arbitrarily the claim with a single STARK.
     Succinct(SuccinctReceipt<Unknown>),
+
+    /// A [Groth16Receipt](groth16::Groth16Receipt), proving arbitrarily the claim with a single
+    /// Groth16 SNARK.
+    #[cfg(feature = "groth16")]
+    Groth16(groth16::Groth16Receipt<Unknown>),
+
+    /// A [FakeReceipt](fake::FakeReceipt), that carries the claim without proving it.
+    #[cfg(feature = "unsafe-fake")]
+    Fake(fake::FakeReceipt<Unknown>),
 }
 
 impl InnerAssumptionReceipt {
@@ -208,10 +1171,27 @@ impl InnerAssumptionReceipt {
         &self,
         ctx: &VerifierContext<SC, RC>,
     ) -> Result<(), VerificationError> {
-        log::debug!("InnerAssumptionReceipt::verify_integrity_with_context");
+        debug!("InnerAssumptionReceipt::verify_integrity_with_context");
         match self {
             Self::Composite(inner) => inner.verify_integrity_with_context(ctx),
             Self::Succinct(inner) => inner.verify_integrity_with_context(ctx),
+            #[cfg(feature = "groth16")]
+            Self::Groth16(inner) => inner.verify_integrity_with_context(ctx),
+            #[cfg(feature = "unsafe-fake")]
+            Self::Fake(inner) => inner.verify_integrity(),
+        }
+    }
+
+    /// Check the structure of this receipt without verifying it: refer to
+    /// [`Proof::validate_structure`].
+    pub fn validate_structure(&self) -> Result<(), VerificationError> {
+        match self {
+            Self::Composite(inner) => inner.validate_structure(),
+            Self::Succinct(inner) => inner.validate_structure(),
+            #[cfg(feature = "groth16")]
+            Self::Groth16(inner) => inner.validate_structure(),
+            #[cfg(feature = "unsafe-fake")]
+            Self::Fake(_) => Ok(()),
         }
     }
 
@@ -233,6 +1213,57 @@ impl InnerAssumptionReceipt {
         }
     }
 
+    /// Returns the [InnerAssumptionReceipt::Groth16] arm.
+    #[cfg(feature = "groth16")]
+    pub fn groth16(&self) -> Result<&groth16::Groth16Receipt<Unknown>, VerificationError> {
+        if let Self::Groth16(x) = self {
+            Ok(x)
+        } else {
+            Err(VerificationError::ReceiptFormatError)
+        }
+    }
+
+    /// Convert this receipt back into an [InnerReceipt], the inverse of the
+    /// `From<InnerReceipt>` conversion: the composite receipts are just re-wrapped, while the
+    /// [Unknown] claim of the other arms becomes a pruned [ReceiptClaim] with the same digest.
+    ///
+    /// The claim of an assumption receipt carries only its digest, so its shape can't be checked
+    /// here: verify the converted receipt with
+    /// [`Proof::verify_claim_digest`](crate::Proof::verify_claim_digest) or [`Proof::verify`].
+    pub fn try_into_receipt(self) -> Result<InnerReceipt, VerificationError> {
+        Ok(match self {
+            Self::Composite(inner) => InnerReceipt::Composite(inner),
+            Self::Succinct(inner) => InnerReceipt::Succinct(inner.try_into_claim()?),
+            #[cfg(feature = "groth16")]
+            Self::Groth16(inner) => InnerReceipt::Groth16(groth16::Groth16Receipt::new(
+                inner.seal,
+                inner.claim.into_pruned(),
+                inner.verifier_parameters,
+            )),
+            #[cfg(feature = "unsafe-fake")]
+            Self::Fake(inner) => {
+                InnerReceipt::Fake(fake::FakeReceipt::new(inner.claim.into_pruned()))
+            }
+        })
+    }
+
+    /// Verify the integrity of this receipt and check that it attests to the `expected` claim,
+    /// e.g. the [ReceiptClaim] an assumption should carry. The digests are compared first, so a
+    /// wrong claim fails with [`VerificationError::ClaimDigestMismatch`] before the seal is
+    /// verified.
+    pub fn verify_as<T: Digestible, SC: CircuitCoreDef, RC: CircuitCoreDef>(
+        &self,
+        ctx: &VerifierContext<SC, RC>,
+        expected: &T,
+    ) -> Result<(), VerificationError> {
+        let (expected, received) = (expected.digest(), self.claim_digest()?);
+        if !sha::digest_eq(&expected, &received) {
+            debug!("assumption claim digest mismatch: expected {expected}, received {received}");
+            return Err(VerificationError::ClaimDigestMismatch { expected, received });
+        }
+        self.verify_integrity_with_context(ctx)
+    }
+
     /// Extract the claim digest from this receipt.
     ///
     /// Note that only the claim digest is available because the claim type may be unknown.
@@ -240,6 +1271,10 @@ impl InnerAssumptionReceipt {
         match self {
             Self::Composite(ref inner) => Ok(inner.claim()?.digest()),
             Self::Succinct(ref inner) => Ok(inner.claim.digest()),
+            #[cfg(feature = "groth16")]
+            Self::Groth16(ref inner) => Ok(inner.claim.digest()),
+            #[cfg(feature = "unsafe-fake")]
+            Self::Fake(ref inner) => Ok(inner.claim.digest()),
         }
     }
 
@@ -248,6 +1283,10 @@ impl InnerAssumptionReceipt {
         match self {
             Self::Composite(ref inner) => inner.verifier_parameters,
             Self::Succinct(ref inner) => inner.verifier_parameters,
+            #[cfg(feature = "groth16")]
+            Self::Groth16(ref inner) => inner.verifier_parameters,
+            #[cfg(feature = "unsafe-fake")]
+            Self::Fake(_) => Digest::ZERO,
         }
     }
 }
@@ -257,6 +1296,10 @@ impl From<InnerReceipt> for InnerAssumptionReceipt {
         match value {
             InnerReceipt::Composite(x) => InnerAssumptionReceipt::Composite(x),
             InnerReceipt::Succinct(x) => InnerAssumptionReceipt::Succinct(x.into_unknown()),
+            #[cfg(feature = "groth16")]
+            InnerReceipt::Groth16(x) => InnerAssumptionReceipt::Groth16(x.into_unknown()),
+            #[cfg(feature = "unsafe-fake")]
+            InnerReceipt::Fake(x) => InnerAssumptionReceipt::Fake(x.into_unknown()),
         }
     }
 }
@@ -266,3 +1309,130 @@ impl From<composite::CompositeReceipt> for InnerReceipt {
         Self::Composite(value)
     }
 }
+
+impl From<SuccinctReceipt<ReceiptClaim>> for InnerReceipt {
+    fn from(value: SuccinctReceipt<ReceiptClaim>) -> Self {
+        Self::Succinct(value)
+    }
+}
+
+#[cfg(feature = "groth16")]
+impl From<groth16::Groth16Receipt<ReceiptClaim>> for InnerReceipt {
+    fn from(value: groth16::Groth16Receipt<ReceiptClaim>) -> Self {
+        Self::Groth16(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use risc0_zkp::core::digest::digest;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case::min(risc0_zkp::MIN_CYCLES_PO2, 97)]
+    #[case::default(DEFAULT_MAX_PO2, 97)]
+    #[case::po2_22(22, 96)]
+    #[case::po2_24(24, 94)]
+    #[case::out_of_range(usize::MAX, 94)]
+    fn estimate_the_security_bits(#[case] max_po2: usize, #[case] bits: u32) {
+        assert_eq!(security_bits(max_po2), bits);
+    }
+
+    const IMAGE_ID: Digest =
+        digest!("9db9988d9fbcacadf2bd29fc7c60b98bc4234342fe536eb983169eb6cc248009");
+    const PUBS: Digest =
+        digest!("1d64010000000000000000000000000000000000000000000000000000000000");
+
+    fn claim(image_id: Digest, pubs: Digest, exit_code: ExitCode) -> MaybePruned<ReceiptClaim> {
+        MaybePruned::Value(
+            ReceiptClaim::builder()
+                .image_id(image_id)
+                .journal_digest(pubs)
+                .exit_code(exit_code)
+                .build(),
+        )
+    }
+
+    #[test]
+    fn should_detect_the_mismatched_exit_code() {
+        let paused = claim(IMAGE_ID, PUBS, ExitCode::Paused(0));
+
+        assert_eq!(
+            mismatched_exit_code(&paused, IMAGE_ID, PUBS),
+            Some(ExitCode::Paused(0))
+        );
+        assert_eq!(
+            mismatched_exit_code(&claim(IMAGE_ID, PUBS, ExitCode::Halted(1)), IMAGE_ID, PUBS),
+            Some(ExitCode::Halted(1))
+        );
+    }
+
+    #[test]
+    fn should_detect_the_split_exit_code() {
+        let split = MaybePruned::Value(ReceiptClaim {
+            output: None.into(),
+            ..ReceiptClaim::builder()
+                .image_id(IMAGE_ID)
+                .exit_code(ExitCode::SystemSplit)
+                .post_state(MaybePruned::Pruned(PUBS))
+                .build()
+        });
+
+        assert_eq!(
+            mismatched_exit_code(&split, IMAGE_ID, Digest::ZERO),
+            Some(ExitCode::SystemSplit)
+        );
+        assert_eq!(mismatched_exit_code(&split, PUBS, Digest::ZERO), None);
+    }
+
+    #[test]
+    fn should_ignore_the_other_mismatches() {
+        let paused = claim(IMAGE_ID, PUBS, ExitCode::Paused(0));
+
+        assert_eq!(mismatched_exit_code(&paused, PUBS, PUBS), None);
+        assert_eq!(mismatched_exit_code(&paused, IMAGE_ID, IMAGE_ID), None);
+        assert_eq!(
+            mismatched_exit_code(&claim(IMAGE_ID, PUBS, ExitCode::Halted(0)), IMAGE_ID, PUBS),
+            None
+        );
+        assert_eq!(
+            mismatched_exit_code(&MaybePruned::Pruned(paused.digest()), IMAGE_ID, PUBS),
+            None
+        );
+    }
+
+    #[test]
+    fn read_the_journal_words() {
+        let journal = Journal::new(
+            [7_u32, 0xdeadbeef, 1]
+                .iter()
+                .flat_map(|w| w.to_le_bytes())
+                .collect(),
+        );
+
+        assert_eq!(
+            journal.words().unwrap().collect::<Vec<_>>(),
+            [7, 0xdeadbeef, 1]
+        );
+        assert_eq!(journal.read_u32_at(1), Some(0xdeadbeef));
+        assert_eq!(journal.read_u32_at(3), None);
+        assert_eq!(Journal::default().words().unwrap().count(), 0);
+
+        let unaligned = Journal::new(alloc::vec![1, 0, 0, 0, 2]);
+        assert_eq!(
+            unaligned.words().err(),
+            Some(JournalDecodeError::TrailingBytes)
+        );
+        assert_eq!(unaligned.read_u32_at(0), None);
+    }
+
+    #[test]
+    fn journal_matches_its_sha256_digest() {
+        let journal = Journal::new(alloc::vec![1, 2, 3]);
+
+        assert!(journal.matches_digest(*sha::Impl::hash_bytes(&[1, 2, 3])));
+        assert!(!journal.matches_digest(*sha::Impl::hash_bytes(&[1, 2])));
+        assert!(!journal.matches_digest(Digest::ZERO));
+    }
+}
@@ -19,7 +19,6 @@
 use alloc::{vec, vec::Vec};
 use risc0_binfmt::{Digestible, ExitCode};
 use risc0_zkp::{
-    adapter::PROOF_SYSTEM_INFO,
     core::{digest::Digest, hash::sha},
     verify::VerificationError,
 };
@@ -29,16 +28,20 @@ use serde::{Deserialize, Serialize};
 use super::InnerAssumptionReceipt;
 use crate::{
     circuit::CircuitCoreDef,
-    receipt::succinct::SuccinctReceiptVerifierParameters,
+    observer::VerifyCheck,
     receipt_claim::{Assumption, Output, PrunedValueError, ReceiptClaim},
     segment::SegmentReceipt,
-    VerifierContext,
+    Error, LimitKind, VerifierContext,
 };
 
 /// A receipt composed of one or more [SegmentReceipt] structs proving a single execution with
 /// continuations, and zero or more [InnerAssumptionReceipt](crate::InnerAssumptionReceipt) structs
 /// proving any assumptions.
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "borsh",
+    derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
 pub struct CompositeReceipt {
     /// Segment receipts forming the proof of an execution with continuations.
     pub segments: Vec<SegmentReceipt>,
@@ -55,6 +58,7 @@ pub struct CompositeReceipt {
     /// Acts as a fingerprint to identity differing proof system or circuit versions between a
     /// prover and a verifier. Is not intended to contain the full verifier parameters, which must
     /// be provided by a trusted source (e.g. packaged with the verifier code).
+    #[cfg_attr(feature = "serde-hex", serde(with = "crate::ser::hex::digest"))]
     pub verifier_parameters: Digest,
 }
 
@@ -65,20 +69,233 @@ impl CompositeReceipt {
         &self,
         ctx: &VerifierContext<SC, RC>,
     ) -> Result<(), VerificationError> {
-        log::debug!("CompositeReceipt::verify_integrity_with_context");
-        // Verify the continuation, by verifying every segment receipt in order.
+        debug!("CompositeReceipt::verify_integrity_with_context");
+        self.precheck(ctx)?;
+        // Verify every segment receipt in order.
+        for (idx, receipt) in self.segments.iter().enumerate() {
+            Self::verify_segment(ctx, idx, receipt)?;
+        }
+        self.verify_claims(ctx)
+    }
+
+    /// Verify the integrity of this receipt like
+    /// [verify_integrity_with_context][Self::verify_integrity_with_context], calling `progress`
+    /// with the `(completed, total)` number of segments after each verified segment, e.g. to drive
+    /// a progress bar.
+    pub fn verify_integrity_with_progress<SC: CircuitCoreDef, RC: CircuitCoreDef>(
+        &self,
+        ctx: &VerifierContext<SC, RC>,
+        progress: &mut dyn FnMut(usize, usize),
+    ) -> Result<(), VerificationError> {
+        debug!("CompositeReceipt::verify_integrity_with_progress");
+        self.precheck(ctx)?;
+        let total = self.segments.len();
+        for (idx, receipt) in self.segments.iter().enumerate() {
+            Self::verify_segment(ctx, idx, receipt)?;
+            progress(idx + 1, total);
+        }
+        self.verify_claims(ctx)
+    }
+
+    /// Verify the integrity of this receipt like
+    /// [verify_integrity_with_context][Self::verify_integrity_with_context], but checking the
+    /// segment seals in parallel when the `rayon` feature is enabled.
+    ///
+    /// The hash suites in a [VerifierContext] cannot be shared across threads, so `ctx` is a
+    /// factory used to build one context for every worker thread (e.g. `VerifierContext::v1_2`).
+    /// Once all the segment seals are verified, the continuation and the assumptions are checked
+    /// sequentially. If more than one segment is invalid, the error of the first one is returned.
+    /// Without the `rayon` feature the segments are verified sequentially.
+    pub fn verify_integrity_with_context_parallel<SC, RC, F>(
+        &self,
+        ctx: F,
+    ) -> Result<(), VerificationError>
+    where
+        SC: CircuitCoreDef + Sync,
+        RC: CircuitCoreDef + Sync,
+        F: Fn() -> VerifierContext<SC, RC> + Sync,
+    {
+        debug!("CompositeReceipt::verify_integrity_with_context_parallel");
+        let main_ctx = ctx();
+        self.precheck(&main_ctx)?;
+        #[cfg(feature = "rayon")]
+        {
+            use rayon::prelude::*;
+
+            let results: Vec<Result<(), VerificationError>> = self
+                .segments
+                .par_iter()
+                .enumerate()
+                .map_init(&ctx, |ctx, (idx, receipt)| {
+                    Self::verify_segment(ctx, idx, receipt)
+                })
+                .collect();
+            results.into_iter().collect::<Result<(), _>>()?;
+        }
+        #[cfg(not(feature = "rayon"))]
+        for (idx, receipt) in self.segments.iter().enumerate() {
+            Self::verify_segment(&main_ctx, idx, receipt)?;
+        }
+        self.verify_claims(&main_ctx)
+    }
+
+    /// The total number of cycles of the segments of a receipt for the `SC` circuit, i.e. the sum
+    /// of their [SegmentReceipt::cycles]: it saturates at `u64::MAX` instead of overflowing.
+    /// Fails like [SegmentReceipt::po2] if a segment doesn't declare a valid po2.
+    pub fn total_cycles<SC: CircuitCoreDef>(&self) -> Result<u64, VerificationError> {
+        self.segments.iter().try_fold(0_u64, |total, receipt| {
+            Ok(total.saturating_add(receipt.cycles::<SC>()?))
+        })
+    }
+
+    /// Check that every segment is proven with a control ID allowed by the segment verifier
+    /// parameters of the context, a prerequisite to lift the segments to succinct receipts,
+    /// without verifying the seals. Fails with [VerificationError::ControlVerificationError] for
+    /// the first segment with a control ID that is not allowed.
+    pub fn can_lift<SC: CircuitCoreDef, RC: CircuitCoreDef>(
+        &self,
+        ctx: &VerifierContext<SC, RC>,
+    ) -> Result<(), VerificationError> {
+        let params = ctx
+            .segment_verifier_parameters
+            .as_ref()
+            .ok_or(VerificationError::VerifierParametersMissing)?;
+        for receipt in &self.segments {
+            let control_id = receipt.control_id(ctx)?;
+            if !params.control_ids.contains(&control_id) {
+                debug!(
+                    "segment {} control id {control_id} is not allowed",
+                    receipt.index
+                );
+                return Err(VerificationError::ControlVerificationError { control_id });
+            }
+        }
+        Ok(())
+    }
+
+    /// Check the structure of this receipt without verifying the seals: there should be at least
+    /// one segment and every segment and assumption receipt should be well formed.
+    pub(crate) fn validate_structure(&self) -> Result<(), VerificationError> {
+        if self.segments.is_empty() {
+            debug!("composite receipt has no segments");
+            return Err(VerificationError::ReceiptFormatError);
+        }
+        self.segments
+            .iter()
+            .try_for_each(SegmentReceipt::validate_structure)?;
+        self.assumption_receipts
+            .iter()
+            .try_for_each(InnerAssumptionReceipt::validate_structure)
+    }
+
+    /// Check that no segment is bigger than the `max_po2` of the context, reading just the seal
+    /// headers: a cheap check to run before verifying the seals. Fails with
+    /// [Error::SegmentPo2TooLarge] for the first segment that exceeds it.
+    pub fn check_po2<SC: CircuitCoreDef, RC: CircuitCoreDef>(
+        &self,
+        ctx: &VerifierContext<SC, RC>,
+    ) -> Result<(), Error> {
+        for receipt in &self.segments {
+            let po2 = receipt.po2::<SC>()?;
+            if po2 > ctx.max_po2 {
+                return Err(Error::SegmentPo2TooLarge {
+                    index: receipt.index,
+                    po2,
+                    max: ctx.max_po2,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Check the number of segments and of assumption receipts against the
+    /// [`max_segments`](VerifierContext::max_segments) and the
+    /// [`max_assumptions`](VerifierContext::max_assumptions) of the context, a cheap check that the
+    /// verification runs before the seals. Fails with [Error::LimitExceeded] for the first limit
+    /// exceeded.
+    pub fn check_limits<SC: CircuitCoreDef, RC: CircuitCoreDef>(
+        &self,
+        ctx: &VerifierContext<SC, RC>,
+    ) -> Result<(), Error> {
+        let limits = [
+            (LimitKind::Segments, ctx.max_segments, self.segments.len()),
+            (
+                LimitKind::Assumptions,
+                ctx.max_assumptions,
+                self.assumption_receipts.len(),
+            ),
+        ];
+        for (kind, limit, found) in limits {
+            if let Some(limit) = limit.filter(|&limit| found > limit) {
+                return Err(Error::LimitExceeded { kind, limit, found });
+            }
+        }
+        Ok(())
+    }
+
+    /// The cheap checks run before verifying the seals: the [CompositeReceipt::check_limits],
+    /// reported as a malformed receipt, and the segment size, reported like the seal verification
+    /// does, i.e. with the [VerificationError::ControlVerificationError] of the first segment
+    /// bigger than the `max_po2` of the context. [CompositeReceipt::check_po2] details the latter.
+    fn precheck<SC: CircuitCoreDef, RC: CircuitCoreDef>(
+        &self,
+        ctx: &VerifierContext<SC, RC>,
+    ) -> Result<(), VerificationError> {
+        self.check_limits(ctx).map_err(|e| {
+            debug!("composite receipt rejected: {e}");
+            VerificationError::ReceiptFormatError
+        })?;
+        for receipt in &self.segments {
+            if receipt.po2::<SC>()? > ctx.max_po2 {
+                debug!("segment {} is bigger than the max po2", receipt.index);
+                return Err(VerificationError::ControlVerificationError {
+                    control_id: receipt.control_id(ctx)?,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Verify the segment at index `idx`, notifying the context observer if any.
+    fn verify_segment<SC: CircuitCoreDef, RC: CircuitCoreDef>(
+        ctx: &VerifierContext<SC, RC>,
+        idx: usize,
+        receipt: &SegmentReceipt,
+    ) -> Result<(), VerificationError> {
+        let Some(observer) = ctx.observer.as_ref() else {
+            return receipt.verify_integrity_with_context(ctx);
+        };
+        observer.on_segment_start(idx, receipt.po2::<SC>()? as u32);
+        let result = receipt.verify_integrity_with_context(ctx);
+        observer.on_segment_end(idx, &result);
+        result
+    }
+
+    /// Verify the claims of the verified segments: their chaining and their assumptions.
+    fn verify_claims<SC: CircuitCoreDef, RC: CircuitCoreDef>(
+        &self,
+        ctx: &VerifierContext<SC, RC>,
+    ) -> Result<(), VerificationError> {
+        ctx.observe(VerifyCheck::Continuation, self.verify_continuation())?;
+        ctx.observe(
+            VerifyCheck::Assumptions,
+            self.verify_attached_assumptions(ctx),
+        )
+    }
+
+    /// Verify the chaining of the segments: every segment should start from the state where the
+    /// previous one stopped and all but the last should end with a system split.
+    fn verify_continuation(&self) -> Result<(), VerificationError> {
         let (final_receipt, receipts) = self
             .segments
             .as_slice()
             .split_last()
             .ok_or(VerificationError::ReceiptFormatError)?;
 
-        // Verify each segment and its chaining to the next.
         let mut expected_pre_state_digest = None;
         for receipt in receipts {
-            receipt.verify_integrity_with_context::<SC, RC>(ctx)?;
             let claim = &receipt.claim;
-            log::debug!("claim: {:#?}", claim);
+            debug!("claim: {:#?}", claim);
             if let Some(id) = expected_pre_state_digest {
                 if id != claim.pre.digest::<sha::Impl>() {
                     return Err(VerificationError::ImageVerificationError);
@@ -90,69 +307,109 @@ impl CompositeReceipt {
             if !claim.output.is_none() {
                 return Err(VerificationError::ReceiptFormatError);
             }
-            expected_pre_state_digest = Some(
-                claim
-                    .post
-                    .as_value()
-                    .map_err(|_| VerificationError::ReceiptFormatError)?
-                    .digest::<sha::Impl>(),
-            );
+            expected_pre_state_digest = Some(claim.post.as_value()?.digest::<sha::Impl>());
         }
 
-        // Verify the last receipt in the continuation.
-        final_receipt.verify_integrity_with_context(ctx)?;
-        log::debug!("final: {:#?}", final_receipt.claim);
+        debug!("final: {:#?}", final_receipt.claim);
         if let Some(id) = expected_pre_state_digest {
             if id != final_receipt.claim.pre.digest::<sha::Impl>() {
                 return Err(VerificationError::ImageVerificationError);
             }
         }
+        Ok(())
+    }
+
+    /// Verify that all the assumptions made by this receipt are discharged by the given `resolvers`.
+    ///
+    /// Every assumption is matched by claim digest against the `resolvers`, in any order, then the
+    /// integrity of the matched receipt is verified. Fails with
+    /// [VerificationError::UnresolvedAssumption] if an assumption has no resolver. The segments of
+    /// this receipt are not verified: use
+    /// [verify_integrity_with_context][Self::verify_integrity_with_context] for that.
+    pub fn verify_assumptions<SC: CircuitCoreDef, RC: CircuitCoreDef>(
+        &self,
+        ctx: &VerifierContext<SC, RC>,
+        resolvers: &[InnerAssumptionReceipt],
+    ) -> Result<(), VerificationError> {
+        debug!("CompositeReceipt::verify_assumptions");
+        for assumption in self.assumptions()? {
+            let receipt = resolvers
+                .iter()
+                .find(|receipt| receipt.claim_digest().ok() == Some(assumption.claim))
+                .ok_or_else(|| {
+                    debug!("no resolver for assumption: {assumption:?}");
+                    VerificationError::UnresolvedAssumption {
+                        digest: assumption.claim,
+                    }
+                })?;
+            Self::verify_assumption(ctx, &assumption, receipt)?;
+        }
+        Ok(())
+    }
+
+    /// The claim digests of the assumptions made by this receipt, in order, e.g. to match them
+    /// with the receipts that resolve them without verifying anything: empty if it makes no
+    /// assumptions. Fails with [VerificationError::ReceiptFormatError] if the output of the last
+    /// segment, its assumptions list or any assumption in it is pruned.
+    pub fn assumption_digests(&self) -> Result<Vec<Digest>, VerificationError> {
+        Ok(self
+            .assumptions()?
+            .into_iter()
+            .map(|assumption| assumption.claim)
+            .collect())
+    }
 
-        // Verify all assumptions on the receipt are resolved by attached receipts.
+    /// Verify all assumptions on the receipt are resolved by attached receipts.
+    fn verify_attached_assumptions<SC: CircuitCoreDef, RC: CircuitCoreDef>(
+        &self,
+        ctx: &VerifierContext<SC, RC>,
+    ) -> Result<(), VerificationError> {
         // Ensure that there is one receipt for every assumption. An explicity check is required
         // because zip will terminate if either iterator terminates.
         let assumptions = self.assumptions()?;
         if assumptions.len() != self.assumption_receipts.len() {
-            log::debug!(
+            debug!(
                 "only {} receipts provided for {} assumptions",
                 assumptions.len(),
                 self.assumption_receipts.len()
             );
             return Err(VerificationError::ReceiptFormatError);
         }
-        for (assumption, receipt) in assumptions.into_iter().zip(self.assumption_receipts.iter()) {
-            let assumption_ctx = match assumption.control_root {
-                // If the control root is all zeroes, we should use the same verifier parameters.
-                Digest::ZERO => None,
-                // Otherwise, we should verify the assumption receipt using the guest-provided root.
-                control_root => Some(
-                    VerifierContext::empty(ctx.circuit, ctx.recursive_circuit)
-                        .with_suites(ctx.suites.clone())
-                        .with_succinct_verifier_parameters(SuccinctReceiptVerifierParameters {
-                            control_root,
-                            inner_control_root: None,
-                            proof_system_info: PROOF_SYSTEM_INFO,
-                            circuit_info: RC::CIRCUIT_INFO,
-                        }),
-                ),
-            };
-            log::debug!("verifying assumption: {assumption:?}");
-            receipt.verify_integrity_with_context(assumption_ctx.as_ref().unwrap_or(ctx))?;
-            if receipt.claim_digest()? != assumption.claim {
-                log::debug!(
-                    "verifying assumption failed due to claim mismatch: assumption: {assumption:?}, receipt claim digest: {}",
-                    receipt.claim_digest()?
-                );
-                return Err(VerificationError::ClaimDigestMismatch {
-                    expected: assumption.claim,
-                    received: receipt.claim_digest()?,
-                });
-            }
+        for (assumption, receipt) in assumptions.iter().zip(self.assumption_receipts.iter()) {
+            Self::verify_assumption(ctx, assumption, receipt)?;
         }
 
         Ok(())
     }
 
+    /// Verify that `receipt` proves the `assumption`.
+    fn verify_assumption<SC: CircuitCoreDef, RC: CircuitCoreDef>(
+        ctx: &VerifierContext<SC, RC>,
+        assumption: &Assumption,
+        receipt: &InnerAssumptionReceipt,
+    ) -> Result<(), VerificationError> {
+        let assumption_ctx = match assumption.control_root {
+            // If the control root is all zeroes, we should use the same verifier parameters.
+            Digest::ZERO => None,
+            // Otherwise, we should verify the assumption receipt using the guest-provided root,
+            // with the same policy.
+            control_root => Some(ctx.for_assumption_root(control_root)),
+        };
+        debug!("verifying assumption: {assumption:?}");
+        receipt.verify_integrity_with_context(assumption_ctx.as_ref().unwrap_or(ctx))?;
+        if !crate::sha::digest_eq(&receipt.claim_digest()?, &assumption.claim) {
+            debug!(
+                "verifying assumption failed due to claim mismatch: assumption: {assumption:?}, receipt claim digest: {}",
+                receipt.claim_digest()?
+            );
+            return Err(VerificationError::ClaimDigestMismatch {
+                expected: assumption.claim,
+                received: receipt.claim_digest()?,
+            });
+        }
+        Ok(())
+    }
+
     /// Returns the [ReceiptClaim] for this [CompositeReceipt].
     pub fn claim(&self) -> Result<ReceiptClaim, VerificationError> {
         let first_claim = &self
@@ -171,8 +428,7 @@ impl CompositeReceipt {
         // TODO(#982) Support unresolved assumptions here by only removing the proven assumptions.
         let output = last_claim
             .output
-            .as_value()
-            .map_err(|_| VerificationError::ReceiptFormatError)?
+            .as_value()?
             .as_ref()
             .map(|output| Output {
                 journal: output.journal.clone(),
@@ -189,6 +445,34 @@ impl CompositeReceipt {
         })
     }
 
+    /// Returns the number of segments in this receipt.
+    pub fn segment_count(&self) -> usize {
+        self.segments.len()
+    }
+
+    /// Returns an iterator over the [SegmentReceipt]s of this receipt, in execution order.
+    pub fn segments(&self) -> impl Iterator<Item = &SegmentReceipt> {
+        self.segments.iter()
+    }
+
+    /// Returns an iterator over the [ReceiptClaim]s of the segments, in execution order, e.g. to
+    /// inspect their pre and post [SystemState][crate::SystemState]. A segment claim with a pruned
+    /// pre or post state yields [VerificationError::ReceiptFormatError].
+    ///
+    /// The claims are not verified: call [CompositeReceipt::verify_integrity_with_context] for that.
+    pub fn iter_segment_claims(
+        &self,
+    ) -> impl Iterator<Item = Result<ReceiptClaim, VerificationError>> + '_ {
+        self.segments.iter().map(|segment| {
+            let claim = &segment.claim;
+            if claim.pre_state().is_err() || claim.post_state().is_err() {
+                debug!("segment {} claim has a pruned state", segment.index);
+                return Err(VerificationError::ReceiptFormatError);
+            }
+            Ok(claim.clone())
+        })
+    }
+
     fn assumptions(&self) -> Result<Vec<Assumption>, VerificationError> {
         // Collect the assumptions from the output of the last segment, handling any pruned values
         // encountered and returning and empty list if the output is None.
@@ -198,8 +482,7 @@ impl CompositeReceipt {
             .ok_or(VerificationError::ReceiptFormatError)?
             .claim
             .output
-            .as_value()
-            .map_err(|_| VerificationError::ReceiptFormatError)?
+            .as_value()?
             .as_ref()
             .map(|output| match output.assumptions.is_empty() {
                 true => Ok(Default::default()),
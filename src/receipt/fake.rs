@@ -0,0 +1,75 @@
+// Copyright Copyright 2024, Horizen Labs, Inc.
+// Copyright Copyright 2024 RISC Zero, Inc.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Fake receipts, like the risc0 dev mode ones, to run the application tests without proving.
+
+use core::fmt::Debug;
+
+use risc0_binfmt::Digestible;
+use risc0_zkp::verify::VerificationError;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    receipt_claim::{MaybePruned, Unknown},
+    sha,
+};
+
+/// A receipt that carries a claim without any seal: it proves nothing.
+///
+/// It's available only with the `unsafe-fake` feature, that can't be enabled in release builds,
+/// and its integrity check always passes: only the claim is checked against the expected image
+/// ID and journal.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "borsh",
+    derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[non_exhaustive]
+pub struct FakeReceipt<Claim>
+where
+    Claim: Digestible + Debug + Clone + Serialize,
+{
+    /// Claim containing information about the computation that this receipt pretends to prove.
+    pub claim: MaybePruned<Claim>,
+}
+
+impl<Claim> FakeReceipt<Claim>
+where
+    Claim: Digestible + Debug + Clone + Serialize,
+{
+    /// Create a [FakeReceipt] for the given claim.
+    pub fn new(claim: impl Into<MaybePruned<Claim>>) -> Self {
+        Self {
+            claim: claim.into(),
+        }
+    }
+
+    /// Pretend to verify the integrity of this receipt: it always passes.
+    pub fn verify_integrity(&self) -> Result<(), VerificationError> {
+        warn!("FAKE RECEIPT: the receipt integrity is NOT verified, never use it in production");
+        Ok(())
+    }
+
+    /// Prunes the claim, retaining its digest, and converts into a [FakeReceipt] with an unknown
+    /// claim type.
+    pub fn into_unknown(self) -> FakeReceipt<Unknown> {
+        FakeReceipt {
+            claim: MaybePruned::Pruned(self.claim.digest::<sha::Impl>()),
+        }
+    }
+}
@@ -0,0 +1,249 @@
+// Copyright Copyright 2024, Horizen Labs, Inc.
+// Copyright Copyright 2024 RISC Zero, Inc.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+use alloc::vec::Vec;
+use core::fmt::Debug;
+
+use risc0_binfmt::{tagged_struct, Digestible};
+use risc0_groth16::{fr_from_hex_string, split_digest, Seal, Verifier, VerifyingKey};
+use risc0_zkp::{
+    core::{digest::Digest, hash::sha::Sha256},
+    verify::VerificationError,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    circuit::CircuitCoreDef,
+    receipt::VerifierContext,
+    receipt_claim::{MaybePruned, Unknown},
+    sha,
+};
+
+/// A receipt composed of a Groth16 over the BN_254 curve, proving the execution of the zkVM.
+///
+/// A [Groth16Receipt] is obtained by compressing a [SuccinctReceipt][crate::SuccinctReceipt] with
+/// the identity recursion program and wrapping the resulting STARK into a Groth16 SNARK.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "borsh",
+    derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[non_exhaustive]
+pub struct Groth16Receipt<Claim>
+where
+    Claim: Digestible + Debug + Clone + Serialize,
+{
+    /// A Groth16 proof of a zkVM execution with the associated claim.
+    #[cfg_attr(feature = "serde-hex", serde(with = "crate::ser::hex::bytes"))]
+    pub seal: Vec<u8>,
+
+    /// [ReceiptClaim][crate::ReceiptClaim] containing information about the execution that this
+    /// receipt proves.
+    pub claim: MaybePruned<Claim>,
+
+    /// A digest of the verifier parameters that can be used to verify this receipt.
+    ///
+    /// Acts as a fingerprint to identify differing proof system or circuit versions between a
+    /// prover and a verifier. It is not intended to contain the full verifier parameters, which must
+    /// be provided by a trusted source (e.g. packaged with the verifier code).
+    #[cfg_attr(feature = "serde-hex", serde(with = "crate::ser::hex::digest"))]
+    pub verifier_parameters: Digest,
+}
+
+impl<Claim> Groth16Receipt<Claim>
+where
+    Claim: Digestible + Debug + Clone + Serialize,
+{
+    /// Create a [Groth16Receipt] from the given seal, claim, and verifier parameters digest.
+    pub fn new(seal: Vec<u8>, claim: MaybePruned<Claim>, verifier_parameters: Digest) -> Self {
+        Self {
+            seal,
+            claim,
+            verifier_parameters,
+        }
+    }
+
+    /// Verify the integrity of this receipt, ensuring the claim is attested
+    /// to by the seal.
+    pub fn verify_integrity_with_context<SC: CircuitCoreDef, RC: CircuitCoreDef>(
+        &self,
+        ctx: &VerifierContext<SC, RC>,
+    ) -> Result<(), VerificationError> {
+        debug!("Groth16Receipt::verify_integrity_with_context");
+        let params = ctx
+            .groth16_verifier_parameters
+            .as_ref()
+            .ok_or(VerificationError::VerifierParametersMissing)?;
+
+        // The public inputs of the Groth16 circuit are the control root and the claim digest,
+        // each split in two field elements, followed by the BN254 control ID.
+        let (a0, a1) =
+            split_digest(params.control_root).map_err(|_| VerificationError::ReceiptFormatError)?;
+        let (c0, c1) = split_digest(self.claim.digest::<sha::Impl>())
+            .map_err(|_| VerificationError::ReceiptFormatError)?;
+        let mut id_bn254: Digest = params.bn254_control_id;
+        id_bn254.as_mut_bytes().reverse();
+        let id_bn254_fr = fr_from_hex_string(&hex::encode(id_bn254))
+            .map_err(|_| VerificationError::ReceiptFormatError)?;
+        Verifier::new(
+            &Seal::from_vec(&self.seal).map_err(|_| VerificationError::ReceiptFormatError)?,
+            &[a0, a1, c0, c1, id_bn254_fr],
+            &params.verifying_key,
+        )
+        .map_err(|_| VerificationError::ReceiptFormatError)?
+        .verify()
+        .map_err(|_| {
+            debug!("groth16 pairing check failed");
+            VerificationError::InvalidProof
+        })?;
+
+        // Everything passed
+        Ok(())
+    }
+
+    /// Check the structure of this receipt without verifying the seal: the seal should be a
+    /// well formed Groth16 proof encoding.
+    pub(crate) fn validate_structure(&self) -> Result<(), VerificationError> {
+        Seal::from_vec(&self.seal)
+            .map(|_| ())
+            .map_err(|_| VerificationError::ReceiptFormatError)
+    }
+
+    /// Prunes the claim, retaining its digest, and converts into a [Groth16Receipt] with an unknown
+    /// claim type. Can be used to get receipts of a uniform type across heterogeneous claims.
+    pub fn into_unknown(self) -> Groth16Receipt<Unknown> {
+        Groth16Receipt {
+            claim: MaybePruned::Pruned(self.claim.digest::<sha::Impl>()),
+            seal: self.seal,
+            verifier_parameters: self.verifier_parameters,
+        }
+    }
+
+    /// Number of bytes used by the seal for this receipt.
+    pub fn seal_size(&self) -> usize {
+        core::mem::size_of_val(self.seal.as_slice())
+    }
+}
+
+/// Verifier parameters used to verify a [Groth16Receipt].
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Groth16ReceiptVerifierParameters {
+    /// Control root with which the receipt is expected to verify.
+    pub control_root: Digest,
+    /// Control ID, calculated with Poseidon over BN254 scalar field, with which the receipt is
+    /// expected to verify.
+    pub bn254_control_id: Digest,
+    /// Groth16 verifying key with which the receipt is expected to verify.
+    pub verifying_key: VerifyingKey,
+}
+
+impl Digestible for Groth16ReceiptVerifierParameters {
+    /// Hash the [Groth16ReceiptVerifierParameters] to get a digest of the struct.
+    fn digest<S: Sha256>(&self) -> Digest {
+        tagged_struct::<S>(
+            "risc0.Groth16ReceiptVerifierParameters",
+            &[
+                self.control_root,
+                self.bn254_control_id,
+                self.verifying_key.digest::<S>(),
+            ],
+            &[],
+        )
+    }
+}
+
+impl Groth16ReceiptVerifierParameters {
+    /// v1_0 set of parameters used to verify a [Groth16Receipt].
+    pub fn v1_0() -> Self {
+        use crate::circuit::v1_0::recursive as circuit;
+        Self {
+            control_root: circuit::control_id::ALLOWED_CONTROL_ROOT,
+            bn254_control_id: circuit::control_id::BN254_IDENTITY_CONTROL_ID,
+            verifying_key: risc0_groth16::verifying_key(),
+        }
+    }
+    /// v1_1 set of parameters used to verify a [Groth16Receipt].
+    pub fn v1_1() -> Self {
+        use crate::circuit::v1_1::recursive as circuit;
+        Self {
+            control_root: circuit::control_id::ALLOWED_CONTROL_ROOT,
+            bn254_control_id: circuit::control_id::BN254_IDENTITY_CONTROL_ID,
+            verifying_key: risc0_groth16::verifying_key(),
+        }
+    }
+    /// v1_2 set of parameters used to verify a [Groth16Receipt].
+    pub fn v1_2() -> Self {
+        use crate::circuit::v1_2::recursive as circuit;
+        Self {
+            control_root: circuit::control_id::ALLOWED_CONTROL_ROOT,
+            bn254_control_id: circuit::control_id::BN254_IDENTITY_CONTROL_ID,
+            verifying_key: risc0_groth16::verifying_key(),
+        }
+    }
+    /// v1_3 set of parameters used to verify a [Groth16Receipt]: a placeholder till the `1.3`
+    /// control IDs are published.
+    #[cfg(feature = "v1_3")]
+    pub fn v1_3() -> Self {
+        use crate::circuit::v1_3::recursive as circuit;
+        Self {
+            control_root: circuit::control_id::ALLOWED_CONTROL_ROOT,
+            bn254_control_id: circuit::control_id::BN254_IDENTITY_CONTROL_ID,
+            verifying_key: risc0_groth16::verifying_key(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::Groth16ReceiptVerifierParameters;
+    use crate::{sha::Digestible, VerifierVersion};
+    use risc0_zkp::core::digest::{digest, Digest};
+    use rstest::rstest;
+
+    // Check that the verifier parameters has a stable digest (and therefore a stable value). This
+    // struct encodes parameters used in verification, and so this value should be updated if and
+    // only if a change to the verifier parameters is expected. Updating the verifier parameters
+    // will result in incompatibility with previous versions.
+    #[rstest]
+    #[case::v1_0(Groth16ReceiptVerifierParameters::v1_0().digest(), digest!("310fe598e8e3e92fa805bc272d7f587898bb8b68c4d5d7938db884abaa76e15c"))]
+    #[case::v1_1(Groth16ReceiptVerifierParameters::v1_1().digest(), digest!("50bd1769093e74abda3711c315d84d78e3e282173f6304a33272d92abb590ef5"))]
+    #[case::v1_2(Groth16ReceiptVerifierParameters::v1_2().digest(), digest!("c101b42bcacd62e35222b1207223250814d05dd41d41f8cadc1f16f86707ae15"))]
+    fn groth16_receipt_verifier_parameters_is_stable(
+        #[case] computed: Digest,
+        #[case] hardcoded: Digest,
+    ) {
+        assert_eq!(computed, hardcoded);
+    }
+
+    // The verifier version detection uses hardcoded digests that must be kept in sync.
+    #[rstest]
+    #[case::v1_0(Groth16ReceiptVerifierParameters::v1_0(), VerifierVersion::V1_0)]
+    #[case::v1_1(Groth16ReceiptVerifierParameters::v1_1(), VerifierVersion::V1_1)]
+    #[case::v1_2(Groth16ReceiptVerifierParameters::v1_2(), VerifierVersion::V1_2)]
+    fn groth16_receipt_verifier_parameters_match_the_verifier_version(
+        #[case] params: Groth16ReceiptVerifierParameters,
+        #[case] version: VerifierVersion,
+    ) {
+        assert_eq!(params.digest(), version.groth16_verifier_parameters());
+        assert_eq!(
+            VerifierVersion::for_parameters(params.digest()),
+            Some(version)
+        );
+    }
+}
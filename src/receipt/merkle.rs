@@ -23,16 +23,24 @@ use alloc::vec::Vec;
 
 use anyhow::{ensure, Result};
 use risc0_core::field::baby_bear::BabyBear;
-use risc0_zkp::core::{digest::Digest, hash::HashFn};
+use risc0_zkp::{
+    core::{digest::Digest, hash::HashFn},
+    verify::VerificationError,
+};
 use serde::{Deserialize, Serialize};
 
 /// Used to verify inclusion of a given recursion program in the committed set.
-#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "borsh",
+    derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
 pub struct MerkleProof {
     /// Index of the leaf for which inclusion is being proven.
     pub index: u32,
     /// Sibling digests on the path from the root to the leaf.
     /// Does not include the root of the leaf.
+    #[cfg_attr(feature = "serde-hex", serde(with = "crate::ser::hex::digests"))]
     pub digests: Vec<Digest>,
 }
 
@@ -66,3 +74,46 @@ impl MerkleProof {
         cur
     }
 }
+
+/// Verify that `proof` includes the control ID `leaf` in the tree committed by `root`, with the
+/// same convention used to verify the succinct receipts. The `hashfn` is the one of the receipt
+/// hash suite (i.e. Poseidon2 for the risc0 recursion programs).
+pub fn verify_proof(
+    leaf: Digest,
+    proof: &MerkleProof,
+    root: Digest,
+    hashfn: &dyn HashFn<BabyBear>,
+) -> Result<(), VerificationError> {
+    proof
+        .verify(&leaf, &root, hashfn)
+        .map_err(|_| VerificationError::ControlVerificationError { control_id: leaf })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+    use risc0_zkp::core::hash::poseidon2::Poseidon2HashSuite;
+
+    #[test]
+    fn should_verify_the_inclusion_proof() {
+        let suite = Poseidon2HashSuite::new_suite();
+        let hashfn = suite.hashfn.as_ref();
+        let leaves = [Digest::from([1_u32; 8]), Digest::from([2_u32; 8])];
+        let sibling = *hashfn.hash_pair(&leaves[0], &leaves[1]);
+        let other = Digest::from([3_u32; 8]);
+        let root = *hashfn.hash_pair(&sibling, &other);
+        let proof = MerkleProof {
+            index: 1,
+            digests: vec![leaves[0], other],
+        };
+
+        assert_eq!(verify_proof(leaves[1], &proof, root, hashfn), Ok(()));
+        assert_eq!(
+            verify_proof(leaves[0], &proof, root, hashfn),
+            Err(VerificationError::ControlVerificationError {
+                control_id: leaves[0]
+            })
+        );
+    }
+}
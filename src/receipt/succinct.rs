@@ -29,10 +29,13 @@ use risc0_zkp::{
 
 use serde::{Deserialize, Serialize};
 
+#[cfg(feature = "bincode")]
+use crate::ser::SerError;
 use crate::{
     circuit::CircuitCoreDef,
-    receipt::{merkle::MerkleProof, VerifierContext},
-    receipt_claim::{MaybePruned, Unknown},
+    observer::VerifyCheck,
+    receipt::{composite::CompositeReceipt, merkle::MerkleProof, VerifierContext},
+    receipt_claim::{MaybePruned, ReceiptClaim, Unknown},
     sha,
 };
 
@@ -43,8 +46,11 @@ use crate::{
 /// computations, and with an arbitrary number of segments linked via composition.
 ///
 /// [STARK]: https://dev.risczero.com/terminology#stark
-#[derive(Clone, Debug, Serialize, Deserialize)]
-#[cfg_attr(test, derive(PartialEq))]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "borsh",
+    derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
 #[non_exhaustive]
 pub struct SuccinctReceipt<Claim>
 where
@@ -52,10 +58,12 @@ where
 {
     /// The cryptographic seal of this receipt. This seal is a STARK proving an execution of the
     /// recursion circuit.
+    #[cfg_attr(feature = "serde-hex", serde(with = "crate::ser::hex::words"))]
     pub seal: Vec<u32>,
 
     /// The control ID of this receipt, identifying the recursion program that was run (e.g. lift,
     /// join, or resolve).
+    #[cfg_attr(feature = "serde-hex", serde(with = "crate::ser::hex::digest"))]
     pub control_id: Digest,
 
     /// Claim containing information about the computation that this receipt proves.
@@ -72,6 +80,7 @@ where
     /// Acts as a fingerprint to identify differing proof system or circuit versions between a
     /// prover and a verifier. It is not intended to contain the full verifier parameters, which must
     /// be provided by a trusted source (e.g. packaged with the verifier code).
+    #[cfg_attr(feature = "serde-hex", serde(with = "crate::ser::hex::digest"))]
     pub verifier_parameters: Digest,
 
     /// Merkle inclusion proof for control_id against the control root for this receipt.
@@ -82,6 +91,27 @@ impl<Claim> SuccinctReceipt<Claim>
 where
     Claim: Digestible + Debug + Clone + Serialize,
 {
+    /// Assemble a [SuccinctReceipt] from its parts, e.g. to test the handling of a controlled
+    /// claim. Available only with the `test-utils` feature: a receipt is usually deserialized.
+    #[cfg(any(test, feature = "test-utils"))]
+    pub fn from_parts(
+        seal: Vec<u32>,
+        control_id: Digest,
+        claim: MaybePruned<Claim>,
+        hashfn: String,
+        verifier_parameters: Digest,
+        control_inclusion_proof: MerkleProof,
+    ) -> Self {
+        Self {
+            seal,
+            control_id,
+            claim,
+            hashfn,
+            verifier_parameters,
+            control_inclusion_proof,
+        }
+    }
+
     /// Verify the integrity of this receipt, ensuring the claim is attested
     /// to by the seal.
     pub fn verify_integrity_with_context<SC: CircuitCoreDef, RC: CircuitCoreDef>(
@@ -114,11 +144,17 @@ where
             .get(&self.hashfn)
             .ok_or(VerificationError::InvalidHashSuite)?;
 
-        let check_code = |_, control_id: &Digest| -> Result<(), VerificationError> {
+        let check_control_id = |control_id: &Digest| -> Result<(), VerificationError> {
+            if !ctx.is_control_id_allowed(control_id) {
+                debug!("control id {control_id} is not allowed by the context");
+                return Err(VerificationError::ControlVerificationError {
+                    control_id: *control_id,
+                });
+            }
             self.control_inclusion_proof
                 .verify(control_id, &params.control_root, suite.hashfn.as_ref())
                 .map_err(|_| {
-                    log::debug!(
+                    debug!(
                         "failed to verify control inclusion proof for {control_id} against root {} with {}",
                         params.control_root,
                         suite.name,
@@ -128,6 +164,9 @@ where
                     }
                 })
         };
+        let check_code = |_, control_id: &Digest| {
+            ctx.observe(VerifyCheck::ControlId, check_control_id(control_id))
+        };
 
         // Verify the receipt itself is correct, and therefore the encoded globals are
         // reliable.
@@ -154,7 +193,7 @@ where
             .map_err(|_| VerificationError::ReceiptFormatError)?;
 
         if control_root != params.inner_control_root.unwrap_or(params.control_root) {
-            log::debug!(
+            debug!(
                 "succinct receipt does not match the expected control root: decoded: {:#?}, expected: {:?}",
                 control_root,
                 params.inner_control_root.unwrap_or(params.control_root),
@@ -167,8 +206,8 @@ where
         // Verify the output hash matches that data
         let output_hash =
             read_sha_halfs(&mut seal_claim).map_err(|_| VerificationError::ReceiptFormatError)?;
-        if output_hash != self.claim.digest::<sha::Impl>() {
-            log::debug!(
+        if !sha::digest_eq(&output_hash, &self.claim.digest::<sha::Impl>()) {
+            debug!(
                 "succinct receipt claim does not match the output digest: claim: {:#?}, digest expected: {output_hash:?}",
                 self.claim,
             );
@@ -178,11 +217,71 @@ where
         Ok(())
     }
 
+    /// Check the structure of this receipt without verifying the seal: the seal should be longer
+    /// than the outputs of the recursion circuit, that all the supported versions share: see
+    /// [SharedRecursionLayout](crate::circuit::SharedRecursionLayout).
+    pub(crate) fn validate_structure(&self) -> Result<(), VerificationError> {
+        use crate::circuit::SharedRecursionLayout;
+        if self.seal.len() <= SharedRecursionLayout::OUTPUT_SIZE {
+            debug!(
+                "succinct receipt seal is too short: {} words",
+                self.seal.len()
+            );
+            return Err(VerificationError::ReceiptFormatError);
+        }
+        Ok(())
+    }
+
+    /// The control ID leaf and its Merkle inclusion proof against the control root, to check the
+    /// recursion program membership elsewhere (e.g. on-chain). The leaf is bound to the seal only
+    /// by [`SuccinctReceipt::verify_integrity_with_context`].
+    pub fn control_inclusion(&self) -> (Digest, MerkleProof) {
+        (self.control_id, self.control_inclusion_proof.clone())
+    }
+
     /// Return the seal for this receipt, as a vector of bytes.
     pub fn get_seal_bytes(&self) -> Vec<u8> {
         self.seal.iter().flat_map(|x| x.to_le_bytes()).collect()
     }
 
+    /// Verify the integrity of this receipt and check that it proves the claim with the given
+    /// digest. This is the way to check a receipt whose claim type is not known, like the
+    /// [Unknown] one of the assumption receipts.
+    pub fn verify_integrity_against<SC: CircuitCoreDef, RC: CircuitCoreDef>(
+        &self,
+        ctx: &VerifierContext<SC, RC>,
+        expected_claim_digest: Digest,
+    ) -> Result<(), VerificationError> {
+        self.verify_integrity_with_context(ctx)?;
+        let received = self.claim.digest::<sha::Impl>();
+        if !sha::digest_eq(&received, &expected_claim_digest) {
+            debug!("succinct receipt claim {received} does not match the expected {expected_claim_digest}");
+            return Err(VerificationError::ClaimDigestMismatch {
+                expected: expected_claim_digest,
+                received,
+            });
+        }
+        Ok(())
+    }
+
+    /// Check, without verifying either receipt, that this receipt proves the same claim as the
+    /// `composite` receipt it was lifted from, comparing the claim digests: a pruned claim of this
+    /// receipt is compared by its digest. Fails with [VerificationError::ClaimDigestMismatch] if
+    /// the claims differ, and like [CompositeReceipt::claim] if the claim of `composite` can't be
+    /// computed, e.g. with [VerificationError::ReceiptFormatError] if its output is pruned.
+    pub fn proves_same_claim_as(
+        &self,
+        composite: &CompositeReceipt,
+    ) -> Result<(), VerificationError> {
+        let expected = composite.claim()?.digest::<sha::Impl>();
+        let received = self.claim.digest::<sha::Impl>();
+        if !sha::digest_eq(&received, &expected) {
+            debug!("succinct receipt claim {received} does not match the composite {expected}");
+            return Err(VerificationError::ClaimDigestMismatch { expected, received });
+        }
+        Ok(())
+    }
+
     /// Number of bytes used by the seal for this receipt.
     pub fn seal_size(&self) -> usize {
         core::mem::size_of_val(self.seal.as_slice())
@@ -190,18 +289,81 @@ where
 
     /// Prunes the claim, retaining its digest, and converts into a [SuccinctReceipt] with an unknown
     /// claim type. Can be used to get receipts of a uniform type across heterogeneous claims.
-    pub fn into_unknown(self) -> SuccinctReceipt<Unknown> {
+    pub fn into_unknown(mut self) -> SuccinctReceipt<Unknown> {
+        // Take the fields instead of moving them: the receipt can implement `Drop`.
         SuccinctReceipt {
             claim: MaybePruned::Pruned(self.claim.digest::<sha::Impl>()),
-            seal: self.seal,
+            seal: core::mem::take(&mut self.seal),
             control_id: self.control_id,
-            hashfn: self.hashfn,
+            hashfn: core::mem::take(&mut self.hashfn),
             verifier_parameters: self.verifier_parameters,
-            control_inclusion_proof: self.control_inclusion_proof,
+            control_inclusion_proof: self.control_inclusion_proof.clone(),
         }
     }
 }
 
+impl SuccinctReceipt<Unknown> {
+    /// Recover the [ReceiptClaim] type of a receipt erased with [SuccinctReceipt::into_unknown].
+    /// An [Unknown] claim is always pruned, so the claim stays pruned with the same digest: its
+    /// shape is checked only by verifying the receipt against the expected claim digest.
+    pub fn try_into_claim(mut self) -> Result<SuccinctReceipt<ReceiptClaim>, VerificationError> {
+        Ok(SuccinctReceipt {
+            claim: self.claim.clone().into_pruned(),
+            seal: core::mem::take(&mut self.seal),
+            control_id: self.control_id,
+            hashfn: core::mem::take(&mut self.hashfn),
+            verifier_parameters: self.verifier_parameters,
+            control_inclusion_proof: self.control_inclusion_proof.clone(),
+        })
+    }
+}
+
+/// Scrub the seal: with the `zeroize` feature it also happens on drop.
+#[cfg(feature = "zeroize")]
+impl<Claim> zeroize::Zeroize for SuccinctReceipt<Claim>
+where
+    Claim: Digestible + Debug + Clone + Serialize,
+{
+    fn zeroize(&mut self) {
+        self.seal.zeroize();
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl<Claim> Drop for SuccinctReceipt<Claim>
+where
+    Claim: Digestible + Debug + Clone + Serialize,
+{
+    fn drop(&mut self) {
+        zeroize::Zeroize::zeroize(self);
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl<Claim> zeroize::ZeroizeOnDrop for SuccinctReceipt<Claim> where
+    Claim: Digestible + Debug + Clone + Serialize
+{
+}
+
+#[cfg(feature = "bincode")]
+impl<Claim> SuccinctReceipt<Claim>
+where
+    Claim: Digestible + Debug + Clone + Serialize + serde::de::DeserializeOwned,
+{
+    /// Serialize just this receipt with the same pinned `bincode` configuration of
+    /// [Proof::to_bincode()][crate::Proof::to_bincode], without the [Proof][crate::Proof]
+    /// wrapper.
+    pub fn to_bytes(&self) -> Result<Vec<u8>, SerError> {
+        crate::ser::to_bincode(self)
+    }
+
+    /// Deserialize a receipt encoded with [SuccinctReceipt::to_bytes()]. Trailing bytes are
+    /// rejected.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, SerError> {
+        crate::ser::from_bincode(bytes)
+    }
+}
+
 /// Verifier parameters used to verify a [SuccinctReceipt].
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
 pub struct SuccinctReceiptVerifierParameters {
@@ -272,6 +434,18 @@ impl SuccinctReceiptVerifierParameters {
             circuit_info: circuit::CircuitImpl::CIRCUIT_INFO,
         }
     }
+    /// v1_3 set of parameters used to verify a [SuccinctReceipt]: a placeholder till the `1.3`
+    /// control root is published.
+    #[cfg(feature = "v1_3")]
+    pub fn v1_3() -> Self {
+        use crate::circuit::v1_3::recursive as circuit;
+        Self {
+            control_root: circuit::control_id::ALLOWED_CONTROL_ROOT,
+            inner_control_root: None,
+            proof_system_info: PROOF_SYSTEM_INFO,
+            circuit_info: circuit::CircuitImpl::CIRCUIT_INFO,
+        }
+    }
 }
 
 #[cfg(test)]
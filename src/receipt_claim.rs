@@ -26,16 +26,23 @@ extern crate alloc;
 
 use alloc::{collections::VecDeque, vec::Vec};
 use anyhow::{anyhow, ensure};
-use core::{fmt, ops::Deref};
+use core::{
+    fmt,
+    hash::{Hash, Hasher},
+    ops::Deref,
+};
 
 // use anyhow::{anyhow, ensure};
 use risc0_binfmt::{
     read_sha_halfs, tagged_list, tagged_list_cons, tagged_struct, write_sha_halfs,
     DecodeError as SysDecodeError, Digestible, ExitCode, InvalidExitCodeError, SystemState,
 };
-use risc0_zkp::core::{
-    digest::Digest,
-    hash::{sha, sha::Sha256},
+use risc0_zkp::{
+    core::{
+        digest::Digest,
+        hash::{sha, sha::Sha256},
+    },
+    verify::VerificationError,
 };
 use serde::{Deserialize, Serialize};
 
@@ -49,8 +56,11 @@ use serde::{Deserialize, Serialize};
 /// state (i.e. the state of memory). [ReceiptClaim] is a "Merkle-ized struct" supporting
 /// partial openings of the underlying fields from a hash commitment to the full structure. Also
 /// see [MaybePruned].
-#[derive(Clone, Debug, Serialize, Deserialize)]
-#[cfg_attr(test, derive(PartialEq))]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(
+    feature = "borsh",
+    derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
 pub struct ReceiptClaim {
     /// The [SystemState] just before execution has begun.
     pub pre: MaybePruned<SystemState>,
@@ -91,6 +101,12 @@ impl ReceiptClaim {
         }
     }
 
+    /// Start building the expected [ReceiptClaim] of an execution: refer to
+    /// [ReceiptClaimBuilder] for the defaults.
+    pub fn builder() -> ReceiptClaimBuilder {
+        ReceiptClaimBuilder::default()
+    }
+
     /// Construct a [ReceiptClaim] representing a zkVM execution that eneded in a normal paused
     /// state (i.e. Paused(0)) with the given image ID and journal.
     pub fn paused(
@@ -143,6 +159,160 @@ impl ReceiptClaim {
         write_sha_halfs(flat, &self.output.digest::<sha::Impl>());
         Ok(())
     }
+
+    /// The [SystemState] just before the execution, or an error if it is pruned.
+    pub fn pre_state(&self) -> Result<&SystemState, PrunedValueError> {
+        self.pre.as_value()
+    }
+
+    /// The [SystemState] just after the execution, or an error if it is pruned.
+    pub fn post_state(&self) -> Result<&SystemState, PrunedValueError> {
+        self.post.as_value()
+    }
+
+    /// The digest of the [SystemState] just before the execution: for a claim proven by a
+    /// receipt that is the image ID. It is available even if the state is pruned.
+    pub fn pre_state_digest(&self) -> Digest {
+        self.pre.digest::<sha::Impl>()
+    }
+
+    /// The digest of the [SystemState] just after the execution. It is available even if the state
+    /// is pruned.
+    pub fn post_state_digest(&self) -> Digest {
+        self.post.digest::<sha::Impl>()
+    }
+
+    /// The program counter just before the execution, or an error if the state is pruned.
+    pub fn pre_pc(&self) -> Result<u32, PrunedValueError> {
+        Ok(self.pre_state()?.pc)
+    }
+
+    /// The program counter just after the execution, or an error if the state is pruned.
+    pub fn post_pc(&self) -> Result<u32, PrunedValueError> {
+        Ok(self.post_state()?.pc)
+    }
+
+    /// The digest of the journal committed by the execution, or `None` if the execution has no
+    /// [Output]. It is available even if the journal is pruned, while it is an error if the whole
+    /// output is pruned.
+    pub fn output_journal_digest(&self) -> Result<Option<Digest>, PrunedValueError> {
+        self.output_digest(|output| output.journal.digest::<sha::Impl>())
+    }
+
+    /// The digest of the [Assumptions] made by the execution, or `None` if the execution has no
+    /// [Output]. It is available even if the assumptions are pruned, while it is an error if the
+    /// whole output is pruned.
+    pub fn assumptions_digest(&self) -> Result<Option<Digest>, PrunedValueError> {
+        self.output_digest(|output| output.assumptions.digest::<sha::Impl>())
+    }
+
+    fn output_digest(
+        &self,
+        f: impl FnOnce(&Output) -> Digest,
+    ) -> Result<Option<Digest>, PrunedValueError> {
+        Ok(self.output.as_value()?.as_ref().map(f))
+    }
+}
+
+/// Builder of the [ReceiptClaim] expected for an execution, e.g. to check the claim of a receipt
+/// with [Proof::verify_claim_digest()][crate::Proof::verify_claim_digest].
+///
+/// By default it builds the same claim of [ReceiptClaim::ok()] with a zero image ID and journal
+/// digest: a successful execution (`Halted(0)`) without assumptions that ends in the empty
+/// [SystemState].
+#[derive(Clone, Debug)]
+pub struct ReceiptClaimBuilder {
+    image_id: Digest,
+    journal_digest: Digest,
+    exit_code: ExitCode,
+    post: MaybePruned<SystemState>,
+}
+
+impl Default for ReceiptClaimBuilder {
+    fn default() -> Self {
+        Self {
+            image_id: Digest::ZERO,
+            journal_digest: Digest::ZERO,
+            exit_code: ExitCode::Halted(0),
+            post: MaybePruned::Value(SystemState {
+                pc: 0,
+                merkle_root: Digest::ZERO,
+            }),
+        }
+    }
+}
+
+impl ReceiptClaimBuilder {
+    /// Set the image ID of the guest, i.e. the digest of the pre execution [SystemState].
+    pub fn image_id(mut self, image_id: impl Into<Digest>) -> Self {
+        self.image_id = image_id.into();
+        self
+    }
+
+    /// Set the digest of the journal committed by the guest.
+    pub fn journal_digest(mut self, journal_digest: impl Into<Digest>) -> Self {
+        self.journal_digest = journal_digest.into();
+        self
+    }
+
+    /// Set the [ExitCode] of the execution.
+    pub fn exit_code(mut self, exit_code: ExitCode) -> Self {
+        self.exit_code = exit_code;
+        self
+    }
+
+    /// Set the [SystemState] just after the execution, or its digest.
+    pub fn post_state(mut self, post: impl Into<MaybePruned<SystemState>>) -> Self {
+        self.post = post.into();
+        self
+    }
+
+    /// Build the [ReceiptClaim].
+    pub fn build(self) -> ReceiptClaim {
+        ReceiptClaim {
+            pre: MaybePruned::Pruned(self.image_id),
+            post: self.post,
+            exit_code: self.exit_code,
+            input: None.into(),
+            output: Some(Output {
+                journal: MaybePruned::Pruned(self.journal_digest),
+                assumptions: MaybePruned::Pruned(Digest::ZERO),
+            })
+            .into(),
+        }
+    }
+}
+
+/// Helpers to interpret the [ExitCode] of a [ReceiptClaim], e.g.
+/// `claim.exit_code.is_success()`.
+pub trait ExitCodeExt {
+    /// Whether the guest halted with a zero exit code: the only successful termination.
+    fn is_success(&self) -> bool;
+
+    /// The exit code set by the guest when it halted or paused, `None` for the terminations
+    /// initiated by the host.
+    fn as_user_code(&self) -> Option<u32>;
+}
+
+impl ExitCodeExt for ExitCode {
+    fn is_success(&self) -> bool {
+        self.is_ok()
+    }
+
+    fn as_user_code(&self) -> Option<u32> {
+        match self {
+            Self::Halted(code) | Self::Paused(code) => Some(*code),
+            Self::SystemSplit | Self::SessionLimit => None,
+        }
+    }
+}
+
+/// The digest of the claim of an execution of `image_id` that committed the journal with the
+/// given SHA-256 digest and terminated with `exit_code`, like [ReceiptClaim::ok()] with a pruned
+/// journal: e.g. to match a proof against a key computed before receiving it. No receipt is
+/// needed.
+pub fn claim_digest_for(image_id: Digest, journal_digest: Digest, exit_code: ExitCode) -> Digest {
+    ReceiptClaim::with_image_id(image_id, journal_digest, exit_code).digest::<sha::Impl>()
 }
 
 impl Digestible for ReceiptClaim {
@@ -162,6 +332,162 @@ impl Digestible for ReceiptClaim {
     }
 }
 
+// Upstream [SystemState] and [ExitCode] only implement `PartialEq`, but their equality is total.
+impl Eq for ReceiptClaim {}
+
+/// Hash the [ReceiptClaim] through its digest: equal claims always have the same digest.
+impl Hash for ReceiptClaim {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.digest::<sha::Impl>().hash(state);
+    }
+}
+
+impl ReceiptClaim {
+    /// The claim of an execution of `image_id` that committed the journal with the given SHA-256
+    /// digest and terminated with `exit_code`: the claim whose digest is [claim_digest_for()].
+    pub fn with_image_id(
+        image_id: impl Into<Digest>,
+        journal_digest: impl Into<Digest>,
+        exit_code: ExitCode,
+    ) -> ReceiptClaim {
+        ReceiptClaim {
+            exit_code,
+            ..ReceiptClaim::ok(image_id, MaybePruned::Pruned(journal_digest.into()))
+        }
+    }
+
+    /// The digests of the fields this claim digest is built from, e.g. to see how the image ID,
+    /// that is the pre state digest, feeds into it. They are available even if the fields are
+    /// pruned.
+    pub fn debug_claim_fields(&self) -> ClaimFields {
+        ClaimFields {
+            pre: self.pre.digest::<sha::Impl>(),
+            post: self.post.digest::<sha::Impl>(),
+            exit_code: self.exit_code,
+            input: self.input.digest::<sha::Impl>(),
+            output: self.output.digest::<sha::Impl>(),
+        }
+    }
+
+    /// Compare this claim with the `expected` one field by field, through their digests. The
+    /// journal and the assumptions are compared only if both the outputs are not pruned.
+    pub fn diff(&self, expected: &ReceiptClaim) -> ClaimDiff {
+        let (journal, assumptions, output) =
+            match (expected.output.as_value(), self.output.as_value()) {
+                (Ok(Some(expected)), Ok(Some(found))) => (
+                    Mismatch::of_digests(&expected.journal, &found.journal),
+                    Mismatch::of_digests(&expected.assumptions, &found.assumptions),
+                    None,
+                ),
+                _ => (
+                    None,
+                    None,
+                    Mismatch::of_digests(&expected.output, &self.output),
+                ),
+            };
+        ClaimDiff {
+            claim: None,
+            pre: Mismatch::of_digests(&expected.pre, &self.pre),
+            post: Mismatch::of_digests(&expected.post, &self.post),
+            exit_code: (expected.exit_code != self.exit_code).then_some(Mismatch {
+                expected: expected.exit_code,
+                found: self.exit_code,
+            }),
+            input: Mismatch::of_digests(&expected.input, &self.input),
+            output,
+            journal,
+            assumptions,
+        }
+    }
+}
+
+/// The digests of the fields of a [ReceiptClaim], as returned by
+/// [ReceiptClaim::debug_claim_fields]: the claim digest is the tagged hash of the input, pre, post
+/// and output digests and of the exit code.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ClaimFields {
+    /// The pre execution [SystemState] digest, i.e. the image ID.
+    pub pre: Digest,
+    /// The post execution [SystemState] digest.
+    pub post: Digest,
+    /// The exit code.
+    pub exit_code: ExitCode,
+    /// The [Input] digest.
+    pub input: Digest,
+    /// The [Output] digest.
+    pub output: Digest,
+}
+
+/// An expected value and the different one that was found instead.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Mismatch<T> {
+    /// The expected value.
+    pub expected: T,
+    /// The value that was found.
+    pub found: T,
+}
+
+impl Mismatch<Digest> {
+    fn of_digests<T: Digestible>(expected: &T, found: &T) -> Option<Self> {
+        let (expected, found) = (expected.digest::<sha::Impl>(), found.digest::<sha::Impl>());
+        (expected != found).then_some(Self { expected, found })
+    }
+}
+
+/// The fields of a [ReceiptClaim] that differ from the expected ones, as returned by
+/// [ReceiptClaim::diff] and [Proof::explain_mismatch()][crate::Proof::explain_mismatch]. The
+/// matching fields are `None`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ClaimDiff {
+    /// The whole claim digest: set only if the claim is pruned, and so its fields are unknown.
+    pub claim: Option<Mismatch<Digest>>,
+    /// The pre execution [SystemState] digest, i.e. the image ID.
+    pub pre: Option<Mismatch<Digest>>,
+    /// The post execution [SystemState] digest.
+    pub post: Option<Mismatch<Digest>>,
+    /// The exit code.
+    pub exit_code: Option<Mismatch<ExitCode>>,
+    /// The [Input] digest.
+    pub input: Option<Mismatch<Digest>>,
+    /// The whole [Output] digest: set only if an output is pruned, and so its fields are unknown.
+    pub output: Option<Mismatch<Digest>>,
+    /// The journal digest.
+    pub journal: Option<Mismatch<Digest>>,
+    /// The [Assumptions] digest.
+    pub assumptions: Option<Mismatch<Digest>>,
+}
+
+impl ClaimDiff {
+    /// Returns `true` if no field differs.
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+impl fmt::Display for ClaimDiff {
+    /// List the names of the fields that differ, like `image id, journal`.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let fields = [
+            ("claim", self.claim.is_some()),
+            ("image id", self.pre.is_some()),
+            ("post state", self.post.is_some()),
+            ("exit code", self.exit_code.is_some()),
+            ("input", self.input.is_some()),
+            ("output", self.output.is_some()),
+            ("journal", self.journal.is_some()),
+            ("assumptions", self.assumptions.is_some()),
+        ];
+        let mut differs = fields.iter().filter(|(_, differs)| *differs);
+        match differs.next() {
+            None => f.write_str("no difference"),
+            Some((name, _)) => {
+                f.write_str(name)?;
+                differs.try_for_each(|(name, _)| write!(f, ", {name}"))
+            }
+        }
+    }
+}
+
 /// Error returned when decoding [ReceiptClaim] fails.
 #[derive(Debug, Copy, Clone)]
 pub enum DecodeError {
@@ -180,6 +506,9 @@ impl fmt::Display for DecodeError {
     }
 }
 
+#[cfg(feature = "std")]
+impl std::error::Error for DecodeError {}
+
 impl From<SysDecodeError> for DecodeError {
     fn from(e: SysDecodeError) -> Self {
         Self::Decode(e)
@@ -202,8 +531,7 @@ impl From<InvalidExitCodeError> for DecodeError {
 /// Note that this in an uninhabited type, simmilar to the [never type].
 ///
 /// [never type]: https://doc.rust-lang.org/std/primitive.never.html
-#[derive(Clone, Debug, Serialize, Deserialize)]
-#[cfg_attr(test, derive(PartialEq))]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub enum Unknown {}
 
 impl Digestible for Unknown {
@@ -212,12 +540,42 @@ impl Digestible for Unknown {
     }
 }
 
+impl MaybePruned<Unknown> {
+    /// An [Unknown] value is always pruned: reinterpret its digest as the one of a `T` value.
+    pub(crate) fn into_pruned<T: Digestible + Clone + Serialize>(self) -> MaybePruned<T> {
+        match self {
+            MaybePruned::Value(unknown) => match unknown {},
+            MaybePruned::Pruned(digest) => MaybePruned::Pruned(digest),
+        }
+    }
+}
+
+#[cfg(feature = "borsh")]
+impl borsh::BorshSerialize for Unknown {
+    fn serialize<W: borsh::io::Write>(&self, _writer: &mut W) -> borsh::io::Result<()> {
+        match *self { /* unreachable  */ }
+    }
+}
+
+#[cfg(feature = "borsh")]
+impl borsh::BorshDeserialize for Unknown {
+    fn deserialize_reader<R: borsh::io::Read>(_reader: &mut R) -> borsh::io::Result<Self> {
+        Err(borsh::io::Error::new(
+            borsh::io::ErrorKind::InvalidData,
+            "Unknown is an uninhabited type",
+        ))
+    }
+}
+
 /// Input field in the [ReceiptClaim], committing to a public value accessible to the guest.
 ///
 /// NOTE: This type is currently uninhabited (i.e. it cannot be constructed), and only its digest
 /// is accessible. It may become inhabited in a future release.
-#[derive(Clone, Debug, Serialize, Deserialize)]
-#[cfg_attr(test, derive(PartialEq))]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "borsh",
+    derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
 pub struct Input {
     // Private field to ensure this type cannot be constructed.
     // By making this type uninhabited, it can be populated later without breaking backwards
@@ -233,8 +591,11 @@ impl Digestible for Input {
 }
 
 /// Output field in the [ReceiptClaim], committing to a claimed journal and assumptions list.
-#[derive(Clone, Debug, Serialize, Deserialize)]
-#[cfg_attr(test, derive(PartialEq))]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "borsh",
+    derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
 pub struct Output {
     /// The journal committed to by the guest execution.
     pub journal: MaybePruned<Vec<u8>>,
@@ -268,10 +629,15 @@ impl Digestible for Output {
 /// and remove the assumption.
 ///
 /// [assumption]: https://dev.risczero.com/terminology#assumption
-#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "borsh",
+    derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
 pub struct Assumption {
     /// Commitment to the assumption claim. It may be the digest of a [ReceiptClaim], or it could
     /// be the digest of the claim for a different circuit such as an accelerator.
+    #[cfg_attr(feature = "serde-hex", serde(with = "crate::ser::hex::digest"))]
     pub claim: Digest,
 
     /// Commitment to the set of [recursion programs] that can be used to resolve this assumption.
@@ -286,6 +652,7 @@ pub struct Assumption {
     /// verify this claim is also used to verify the assumption.
     ///
     /// [recursion programs]: https://dev.risczero.com/terminology#recursion-program
+    #[cfg_attr(feature = "serde-hex", serde(with = "crate::ser::hex::digest"))]
     pub control_root: Digest,
 }
 
@@ -297,8 +664,11 @@ impl Digestible for Assumption {
 }
 
 /// A list of assumptions, each a [Digest] or populated value of an [Assumption].
-#[derive(Clone, Default, Debug, Serialize, Deserialize)]
-#[cfg_attr(test, derive(PartialEq))]
+#[derive(Clone, Default, Debug, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "borsh",
+    derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
 pub struct Assumptions(pub Vec<MaybePruned<Assumption>>);
 
 impl Assumptions {
@@ -426,6 +796,10 @@ impl From<Vec<Assumption>> for MaybePruned<Assumptions> {
 /// proof. When a subtree is pruned, the digest commits to the value of all contained fields.
 /// [ReceiptClaim] is the motivating example of this type of Merkle-ized struct.
 #[derive(Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "borsh",
+    derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
 pub enum MaybePruned<T>
 where
     T: Clone + Serialize,
@@ -434,6 +808,7 @@ where
     Value(T),
 
     /// Pruned value, which is a hash [Digest] of the value.
+    #[cfg_attr(feature = "serde-hex", serde(with = "crate::ser::hex::digest"))]
     Pruned(Digest),
 }
 
@@ -457,6 +832,16 @@ where
         }
     }
 
+    /// Returns `true` if only the digest of the value is available.
+    pub fn is_pruned(&self) -> bool {
+        matches!(self, MaybePruned::Pruned(_))
+    }
+
+    /// Apply `f` to the value, or return an error if it is pruned.
+    pub fn map_value<U>(&self, f: impl FnOnce(&T) -> U) -> Result<U, PrunedValueError> {
+        self.as_value().map(f)
+    }
+
     /// Unwrap the value as a mutable reference, or return an error.
     pub fn as_value_mut(&mut self) -> Result<&mut T, PrunedValueError> {
         match self {
@@ -517,7 +902,8 @@ where
     }
 }
 
-#[cfg(test)]
+/// Two [MaybePruned] are equal only if they are both values or both pruned: a value is never
+/// equal to its own pruned digest.
 impl<T> PartialEq for MaybePruned<T>
 where
     T: Clone + Serialize + PartialEq,
@@ -531,6 +917,21 @@ where
     }
 }
 
+impl<T> Eq for MaybePruned<T> where T: Clone + Serialize + Eq {}
+
+impl<T> Hash for MaybePruned<T>
+where
+    T: Clone + Serialize + Hash,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        core::mem::discriminant(self).hash(state);
+        match self {
+            Self::Value(value) => value.hash(state),
+            Self::Pruned(digest) => digest.hash(state),
+        }
+    }
+}
+
 impl<T> fmt::Debug for MaybePruned<T>
 where
     T: Clone + Serialize + Digestible + fmt::Debug,
@@ -550,7 +951,7 @@ where
 }
 
 /// Error returned when the source value was pruned, and is not available.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct PrunedValueError(pub Digest);
 
 impl fmt::Display for PrunedValueError {
@@ -558,3 +959,227 @@ impl fmt::Display for PrunedValueError {
         write!(f, "value is pruned: {}", &self.0)
     }
 }
+
+#[cfg(feature = "std")]
+impl std::error::Error for PrunedValueError {}
+
+/// A value needed by the verification is pruned: the receipt is malformed.
+impl From<PrunedValueError> for VerificationError {
+    fn from(_: PrunedValueError) -> Self {
+        VerificationError::ReceiptFormatError
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case::halted(ExitCode::Halted(0), true, Some(0))]
+    #[case::halted_with_error(ExitCode::Halted(3), false, Some(3))]
+    #[case::paused(ExitCode::Paused(0), false, Some(0))]
+    #[case::system_split(ExitCode::SystemSplit, false, None)]
+    #[case::session_limit(ExitCode::SessionLimit, false, None)]
+    fn interpret_the_exit_code(
+        #[case] exit_code: ExitCode,
+        #[case] success: bool,
+        #[case] user_code: Option<u32>,
+    ) {
+        assert_eq!(exit_code.is_success(), success);
+        assert_eq!(exit_code.as_user_code(), user_code);
+    }
+
+    #[test]
+    fn claim_fields_build_the_claim_digest() {
+        let (image_id, journal_digest) = (Digest::from([1; 8]), Digest::from([2; 8]));
+        let claim = ReceiptClaim::with_image_id(image_id, journal_digest, ExitCode::Halted(3));
+        let fields = claim.debug_claim_fields();
+
+        assert_eq!(
+            claim.digest::<sha::Impl>(),
+            claim_digest_for(image_id, journal_digest, ExitCode::Halted(3))
+        );
+        assert_eq!(fields.pre, image_id);
+        assert_eq!(fields.exit_code, ExitCode::Halted(3));
+        assert_eq!(
+            tagged_struct::<sha::Impl>(
+                "risc0.ReceiptClaim",
+                &[fields.input, fields.pre, fields.post, fields.output],
+                &[0, 3],
+            ),
+            claim.digest::<sha::Impl>()
+        );
+    }
+
+    #[test]
+    fn system_state_accessors_unwrap_the_values() {
+        let image_id = Digest::from([1, 2, 3, 4, 5, 6, 7, 8]);
+        let claim = ReceiptClaim::ok(image_id, MaybePruned::Pruned(Digest::ZERO));
+
+        assert_eq!(claim.pre_state_digest(), image_id);
+        assert!(matches!(claim.pre_state(), Err(PrunedValueError(d)) if d == image_id));
+        assert!(matches!(claim.pre_pc(), Err(PrunedValueError(d)) if d == image_id));
+
+        assert_eq!(claim.post_pc().unwrap(), 0);
+        assert_eq!(claim.post_state().unwrap().merkle_root, Digest::ZERO);
+        assert_eq!(
+            claim.post_state_digest(),
+            claim.post_state().unwrap().digest::<sha::Impl>()
+        );
+    }
+
+    #[test]
+    fn output_accessors_decode_the_output() {
+        let journal = alloc::vec![1, 2, 3];
+        let journal_digest = journal.digest::<sha::Impl>();
+        let image_id = Digest::from([1, 2, 3, 4, 5, 6, 7, 8]);
+        let mut claim = ReceiptClaim::ok(image_id, journal);
+        let empty = Assumptions(Vec::new()).digest::<sha::Impl>();
+
+        assert_eq!(claim.output_journal_digest(), Ok(Some(journal_digest)));
+        assert_eq!(claim.assumptions_digest(), Ok(Some(empty)));
+
+        let output = claim.output.as_value_mut().unwrap().as_mut().unwrap();
+        output.journal = MaybePruned::Pruned(journal_digest);
+        output.assumptions = MaybePruned::Pruned(Digest::from([9; 8]));
+        assert_eq!(claim.output_journal_digest(), Ok(Some(journal_digest)));
+        assert_eq!(claim.assumptions_digest(), Ok(Some(Digest::from([9; 8]))));
+
+        let output_digest = claim.output.digest::<sha::Impl>();
+        claim.output = MaybePruned::Pruned(output_digest);
+        assert_eq!(
+            claim.output_journal_digest(),
+            Err(PrunedValueError(output_digest))
+        );
+        assert_eq!(
+            claim.assumptions_digest(),
+            Err(PrunedValueError(output_digest))
+        );
+
+        claim.output = None.into();
+        assert_eq!(claim.output_journal_digest(), Ok(None));
+        assert_eq!(claim.assumptions_digest(), Ok(None));
+    }
+
+    #[test]
+    fn maybe_pruned_accessors() {
+        let state = SystemState {
+            pc: 0x1000,
+            merkle_root: Digest::ZERO,
+        };
+        let digest = state.digest::<sha::Impl>();
+        let value = MaybePruned::Value(state);
+        let pruned = MaybePruned::<SystemState>::Pruned(digest);
+
+        assert!(!value.is_pruned());
+        assert!(pruned.is_pruned());
+        assert_eq!(value.map_value(|state| state.pc), Ok(0x1000));
+        assert_eq!(
+            pruned.map_value(|state| state.pc),
+            Err(PrunedValueError(digest))
+        );
+        assert_eq!(
+            VerificationError::from(pruned.as_value().unwrap_err()),
+            VerificationError::ReceiptFormatError
+        );
+    }
+
+    #[test]
+    fn builder_defaults_to_the_ok_claim() {
+        let image_id = Digest::from([1, 2, 3, 4, 5, 6, 7, 8]);
+        let journal_digest = Digest::from([8, 7, 6, 5, 4, 3, 2, 1]);
+        let builder = ReceiptClaim::builder()
+            .image_id(image_id)
+            .journal_digest(journal_digest);
+
+        assert_eq!(
+            builder.clone().build().digest::<sha::Impl>(),
+            ReceiptClaim::ok(image_id, MaybePruned::Pruned(journal_digest)).digest::<sha::Impl>()
+        );
+        assert_eq!(
+            builder
+                .clone()
+                .exit_code(ExitCode::Paused(0))
+                .build()
+                .digest::<sha::Impl>(),
+            ReceiptClaim::paused(image_id, MaybePruned::Pruned(journal_digest))
+                .digest::<sha::Impl>()
+        );
+        let post = SystemState {
+            pc: 0x1000,
+            merkle_root: journal_digest,
+        };
+        let claim = builder.post_state(post.clone()).build();
+        assert_eq!(claim.post_pc().unwrap(), 0x1000);
+        assert_eq!(
+            claim.digest::<sha::Impl>(),
+            ReceiptClaim::builder()
+                .image_id(image_id)
+                .journal_digest(journal_digest)
+                .post_state(MaybePruned::Pruned(post.digest::<sha::Impl>()))
+                .build()
+                .digest::<sha::Impl>()
+        );
+    }
+
+    #[test]
+    fn diff_reports_the_different_fields() {
+        let image_id = Digest::from([1, 2, 3, 4, 5, 6, 7, 8]);
+        let expected = ReceiptClaim::ok(image_id, MaybePruned::Pruned(Digest::ZERO));
+        let journal = Digest::from([8, 7, 6, 5, 4, 3, 2, 1]);
+        let found = ReceiptClaim::builder()
+            .image_id(image_id)
+            .journal_digest(journal)
+            .exit_code(ExitCode::Halted(1))
+            .build();
+
+        let diff = found.diff(&expected);
+
+        assert_eq!(
+            diff,
+            ClaimDiff {
+                exit_code: Some(Mismatch {
+                    expected: ExitCode::Halted(0),
+                    found: ExitCode::Halted(1)
+                }),
+                journal: Some(Mismatch {
+                    expected: Digest::ZERO,
+                    found: journal
+                }),
+                ..Default::default()
+            }
+        );
+        assert_eq!(alloc::format!("{diff}"), "exit code, journal");
+        assert!(expected.diff(&expected).is_empty());
+        assert_eq!(alloc::format!("{}", ClaimDiff::default()), "no difference");
+    }
+
+    #[test]
+    fn diff_compares_the_pruned_output() {
+        let expected = ReceiptClaim::ok(Digest::ZERO, MaybePruned::Pruned(Digest::ZERO));
+        let mut found = expected.clone();
+        found.output = MaybePruned::Pruned(Digest::ZERO);
+
+        let diff = found.diff(&expected);
+
+        assert!(diff.output.is_some());
+        assert_eq!(diff.journal, None);
+        assert_eq!(alloc::format!("{diff}"), "output");
+    }
+
+    #[test]
+    fn claim_digest_for_matches_the_built_claims() {
+        let image_id = Digest::from([1, 2, 3, 4, 5, 6, 7, 8]);
+        let journal = Digest::from([8, 7, 6, 5, 4, 3, 2, 1]);
+
+        assert_eq!(
+            claim_digest_for(image_id, journal, ExitCode::Halted(0)),
+            ReceiptClaim::ok(image_id, MaybePruned::Pruned(journal)).digest::<sha::Impl>()
+        );
+        assert_eq!(
+            claim_digest_for(image_id, journal, ExitCode::Paused(0)),
+            ReceiptClaim::paused(image_id, MaybePruned::Pruned(journal)).digest::<sha::Impl>()
+        );
+    }
+}
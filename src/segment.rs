@@ -25,24 +25,32 @@ use risc0_zkp::{
     field::{baby_bear::BabyBearElem, Elem},
     layout::Tree,
     verify::VerificationError,
-    MIN_CYCLES_PO2,
+    MAX_CYCLES_PO2, MIN_CYCLES_PO2,
 };
 
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    circuit::CircuitCoreDef,
+    circuit::{CircuitCoreDef, HashFn},
+    observer::VerifyCheck,
     receipt::DEFAULT_MAX_PO2,
     receipt_claim::{MaybePruned, ReceiptClaim},
     sha, VerifierContext,
 };
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
-#[cfg_attr(test, derive(PartialEq))]
+/// A receipt attesting to the execution of a single segment of a zkVM execution, as part of a
+/// [CompositeReceipt][crate::CompositeReceipt].
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "borsh",
+    derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
 pub struct SegmentReceipt {
+    #[cfg_attr(feature = "serde-hex", serde(with = "crate::ser::hex::words"))]
     pub seal: Vec<u32>,
     pub index: u32,
     pub hashfn: String,
+    #[cfg_attr(feature = "serde-hex", serde(with = "crate::ser::hex::digest"))]
     pub verifier_parameters: Digest,
     pub claim: ReceiptClaim,
 }
@@ -75,13 +83,18 @@ impl SegmentReceipt {
             });
         }
 
-        log::debug!("SegmentReceipt::verify_integrity_with_context");
-        let check_code = |_, control_id: &Digest| -> Result<(), VerificationError> {
-            params.control_ids.contains(control_id).then_some(()).ok_or(
-                VerificationError::ControlVerificationError {
-                    control_id: *control_id,
-                },
-            )
+        debug!("SegmentReceipt::verify_integrity_with_context");
+        // The risc0 verifier panics on a po2 out of range: reject it up front.
+        self.po2::<SC>()?;
+        let check_code = |po2, control_id: &Digest| -> Result<(), VerificationError> {
+            let result = (po2 as usize <= ctx.max_po2
+                && params.control_ids.contains(control_id)
+                && ctx.is_control_id_allowed(control_id))
+            .then_some(())
+            .ok_or(VerificationError::ControlVerificationError {
+                control_id: *control_id,
+            });
+            ctx.observe(VerifyCheck::ControlId, result)
         };
         let suite = ctx
             .suites
@@ -92,8 +105,11 @@ impl SegmentReceipt {
         // Receipt is consistent with the claim encoded on the seal. Now check against the
         // claim on the struct.
         let decoded_claim = decode_receipt_claim_from_seal::<SC>(&self.seal)?;
-        if decoded_claim.digest::<sha::Impl>() != self.claim.digest::<sha::Impl>() {
-            log::debug!(
+        if !sha::digest_eq(
+            &decoded_claim.digest::<sha::Impl>(),
+            &self.claim.digest::<sha::Impl>(),
+        ) {
+            debug!(
                 "decoded segment receipt claim does not match claim field:\ndecoded: {:#?},\nexpected: {:#?}",
                 decoded_claim,
                 self.claim,
@@ -116,6 +132,61 @@ impl SegmentReceipt {
     pub fn seal_size(&self) -> usize {
         core::mem::size_of_val(self.seal.as_slice())
     }
+
+    /// The segment size, as a power of two (po2), declared in the seal header of a receipt for the
+    /// `SC` circuit. Fails with [VerificationError::ReceiptFormatError] if the header doesn't
+    /// declare a po2 in the `MIN_CYCLES_PO2..=MAX_CYCLES_PO2` range.
+    ///
+    /// The value is not verified: call [SegmentReceipt::verify_integrity_with_context] for that.
+    pub fn po2<SC: CircuitCoreDef>(&self) -> Result<usize, VerificationError> {
+        // Like the risc0 verifier, take the raw seal word just after the circuit outputs.
+        let po2 = *self
+            .seal
+            .get(SC::OUTPUT_SIZE)
+            .ok_or(VerificationError::ReceiptFormatError)? as usize;
+        if !(MIN_CYCLES_PO2..=MAX_CYCLES_PO2).contains(&po2) {
+            debug!("segment {} declares an invalid po2 {po2}", self.index);
+            return Err(VerificationError::ReceiptFormatError);
+        }
+        Ok(po2)
+    }
+
+    /// The control ID committed by the seal, i.e. the one the circuit was proven with, read
+    /// without verifying the seal: the verification stops as soon as the control ID is known.
+    pub fn control_id<SC: CircuitCoreDef, RC: CircuitCoreDef>(
+        &self,
+        ctx: &VerifierContext<SC, RC>,
+    ) -> Result<Digest, VerificationError> {
+        // The risc0 verifier panics on a po2 out of range: reject it up front.
+        self.po2::<SC>()?;
+        let suite = ctx
+            .suites
+            .get(self.hashfn.as_str())
+            .ok_or(VerificationError::InvalidHashSuite)?;
+        let found = core::cell::Cell::new(None);
+        let stop = |_, control_id: &Digest| -> Result<(), VerificationError> {
+            found.set(Some(*control_id));
+            Err(VerificationError::ControlVerificationError {
+                control_id: *control_id,
+            })
+        };
+        let _ = risc0_zkp::verify::verify(ctx.circuit, suite, &self.seal, stop);
+        found.get().ok_or(VerificationError::ReceiptFormatError)
+    }
+
+    /// Check the structure of this receipt without verifying the seal: the seal header should
+    /// declare a po2 in range. The header is read with the
+    /// [SharedSegmentLayout](crate::circuit::SharedSegmentLayout) of all the supported versions.
+    pub(crate) fn validate_structure(&self) -> Result<(), VerificationError> {
+        self.po2::<crate::circuit::SharedSegmentLayout>()
+            .map(|_| ())
+    }
+
+    /// The number of cycles of the segment, i.e. `2^po2` where po2 is the one returned by
+    /// [SegmentReceipt::po2].
+    pub fn cycles<SC: CircuitCoreDef>(&self) -> Result<u64, VerificationError> {
+        Ok(1 << self.po2::<SC>()?)
+    }
 }
 
 fn decode_system_state_from_io<E: Elem + Into<u32>>(
@@ -173,6 +244,24 @@ pub(crate) fn decode_receipt_claim_from_seal<SC: CircuitCoreDef>(
     })
 }
 
+/// Scrub the seal: with the `zeroize` feature it also happens on drop.
+#[cfg(feature = "zeroize")]
+impl zeroize::Zeroize for SegmentReceipt {
+    fn zeroize(&mut self) {
+        self.seal.zeroize();
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for SegmentReceipt {
+    fn drop(&mut self) {
+        zeroize::Zeroize::zeroize(self);
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::ZeroizeOnDrop for SegmentReceipt {}
+
 /// Verifier parameters used to verify a [SegmentReceipt].
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
 pub struct SegmentReceiptVerifierParameters {
@@ -209,8 +298,7 @@ impl SegmentReceiptVerifierParameters {
                 POSEIDON2_CONTROL_IDS
                     .into_iter()
                     .chain(SHA256_CONTROL_IDS)
-                    .chain(BLAKE2B_CONTROL_IDS)
-                    .map(Into::into),
+                    .chain(BLAKE2B_CONTROL_IDS),
             ),
             proof_system_info: PROOF_SYSTEM_INFO,
             circuit_info: crate::circuit::v1_0::CircuitImpl::CIRCUIT_INFO,
@@ -221,7 +309,7 @@ impl SegmentReceiptVerifierParameters {
     pub fn v1_1() -> Self {
         use risc0_zkp::adapter::{CircuitInfo, PROOF_SYSTEM_INFO};
         Self::from_max_po2(
-            &crate::circuit::v1_1::control_id,
+            &crate::circuit::v1_1::control_id_by_fn,
             DEFAULT_MAX_PO2,
             PROOF_SYSTEM_INFO,
             crate::circuit::v1_1::CircuitImpl::CIRCUIT_INFO,
@@ -232,24 +320,37 @@ impl SegmentReceiptVerifierParameters {
     pub fn v1_2() -> Self {
         use risc0_zkp::adapter::{CircuitInfo, PROOF_SYSTEM_INFO};
         Self::from_max_po2(
-            &crate::circuit::v1_2::control_id,
+            &crate::circuit::v1_2::control_id_by_fn,
             DEFAULT_MAX_PO2,
             PROOF_SYSTEM_INFO,
             crate::circuit::v1_2::CircuitImpl::CIRCUIT_INFO,
         )
     }
 
-    fn from_max_po2(
-        resolver: &dyn Fn(&str, usize) -> Option<Digest>,
+    /// v1.3 set of parameters used to verify a [SegmentReceipt]: a placeholder till the `1.3`
+    /// control IDs are published.
+    #[cfg(feature = "v1_3")]
+    pub fn v1_3() -> Self {
+        use risc0_zkp::adapter::{CircuitInfo, PROOF_SYSTEM_INFO};
+        Self::from_max_po2(
+            &crate::circuit::v1_3::control_id_by_fn,
+            DEFAULT_MAX_PO2,
+            PROOF_SYSTEM_INFO,
+            crate::circuit::v1_3::CircuitImpl::CIRCUIT_INFO,
+        )
+    }
+
+    pub(crate) fn from_max_po2(
+        resolver: &dyn Fn(HashFn, usize) -> Option<Digest>,
         max_po2: usize,
         proof_system_info: ProtocolInfo,
         circuit_info: ProtocolInfo,
     ) -> Self {
         Self {
             control_ids: BTreeSet::from_iter(
-                ["poseidon2", "sha-256", "blake2b"]
+                HashFn::ALL
                     .into_iter()
-                    .flat_map(|hash_name| control_ids(resolver, hash_name, max_po2)),
+                    .flat_map(|hash| control_ids(resolver, hash, max_po2)),
             ),
             proof_system_info,
             circuit_info,
@@ -257,14 +358,14 @@ impl SegmentReceiptVerifierParameters {
     }
 }
 
-fn control_ids<'a, H: AsRef<str> + 'a>(
-    resolver: &'a dyn Fn(&str, usize) -> Option<Digest>,
-    hash_name: H,
+fn control_ids<'a>(
+    resolver: &'a dyn Fn(HashFn, usize) -> Option<Digest>,
+    hash: HashFn,
     po2_max: usize,
 ) -> impl Iterator<Item = Digest> + 'a {
     // Using `take_while` here ensures termination when po2_max is much greater than the highest po2.
     (MIN_CYCLES_PO2..=po2_max)
-        .map(move |po2| resolver(hash_name.as_ref(), po2))
+        .map(move |po2| resolver(hash, po2))
         .take_while(Option::is_some)
         .map(Option::unwrap)
 }
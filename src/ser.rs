@@ -0,0 +1,220 @@
+// Copyright Copyright 2024, Horizen Labs, Inc.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Pinned binary encodings of the proofs.
+//!
+//! The `bincode` encoding uses fixed size integers in little endian order, the same layout produced
+//! by `bincode::serialize`, and rejects any trailing byte on decoding. Untrusted proofs can be read
+//! from a stream within a [Limits] budget.
+//!
+//! The versioned encoding of [`Proof::to_bytes`](crate::Proof::to_bytes) prepends a magic and
+//! a format version to the `bincode` one, so that a proof written by another version of the
+//! crate fails with a clear error instead of a garbled decoding.
+//!
+//! The `protobuf` encoding follows the messages of `proto/proof.proto`, for the services that
+//! are not written in Rust.
+
+use alloc::string::String;
+use core::fmt;
+
+#[cfg(feature = "serde-hex")]
+pub(crate) mod hex;
+#[cfg(feature = "protobuf")]
+pub(crate) mod proto;
+
+#[cfg(feature = "bincode")]
+use alloc::vec::Vec;
+#[cfg(feature = "bincode")]
+use serde::{de::DeserializeOwned, Serialize};
+
+/// Error returned when (de)serializing a proof fails.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum SerError {
+    /// The `bincode` encoding or decoding failed.
+    Bincode(String),
+    /// The `protobuf` decoding failed.
+    Protobuf(String),
+    /// The composite proof has more segments than the [Limits] allow.
+    TooManySegments {
+        /// The number of segments declared by the proof.
+        count: u64,
+        /// The maximum number of segments allowed.
+        max: usize,
+    },
+    /// The encoded proof is bigger than the [Limits] allow.
+    TooLarge {
+        /// The maximum size allowed, in bytes.
+        max: u64,
+    },
+    /// The bytes don't start with the magic of the versioned encoding.
+    MissingFormatHeader,
+    /// The versioned encoding has a format version that this crate cannot read.
+    UnsupportedProofFormatVersion {
+        /// The format version of the encoded proof.
+        found: u16,
+        /// The format version supported, i.e. [PROOF_FORMAT_VERSION].
+        supported: u16,
+    },
+}
+
+/// The magic that starts a proof encoded by [`Proof::to_bytes`](crate::Proof::to_bytes).
+pub const PROOF_FORMAT_MAGIC: [u8; 4] = *b"R0VP";
+
+/// The format version written by [`Proof::to_bytes`](crate::Proof::to_bytes), after the magic as
+/// a little endian `u16`: the `bincode` encoding of the proof follows. It changes only when the
+/// encoding of the proofs does.
+pub const PROOF_FORMAT_VERSION: u16 = 1;
+
+impl fmt::Display for SerError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Bincode(e) => write!(f, "bincode serialization failed: {e}"),
+            Self::Protobuf(e) => write!(f, "protobuf deserialization failed: {e}"),
+            Self::TooManySegments { count, max } => {
+                write!(f, "proof has {count} segments, more than the max {max}")
+            }
+            Self::TooLarge { max } => write!(f, "proof is bigger than the max {max} bytes"),
+            Self::MissingFormatHeader => f.write_str("missing proof format header"),
+            Self::UnsupportedProofFormatVersion { found, supported } => {
+                write!(
+                    f,
+                    "unsupported proof format version {found}; supported {supported}"
+                )
+            }
+        }
+    }
+}
+
+/// The budget enforced while reading a proof with
+/// [`Proof::from_reader`](crate::Proof::from_reader).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Limits {
+    /// The maximum number of segments of a composite proof.
+    pub max_segments: usize,
+    /// The maximum size of the encoded proof, in bytes.
+    pub max_size: u64,
+}
+
+impl Limits {
+    /// Create the [Limits] with the given maximum number of segments and size in bytes.
+    pub fn new(max_segments: usize, max_size: u64) -> Self {
+        Self {
+            max_segments,
+            max_size,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for SerError {}
+
+#[cfg(feature = "bincode")]
+impl From<bincode::Error> for SerError {
+    fn from(e: bincode::Error) -> Self {
+        use alloc::string::ToString;
+        Self::Bincode(e.to_string())
+    }
+}
+
+#[cfg(feature = "bincode")]
+fn bincode_options() -> impl bincode::Options {
+    use bincode::Options;
+    bincode::DefaultOptions::new()
+        .with_fixint_encoding()
+        .with_little_endian()
+        .reject_trailing_bytes()
+}
+
+#[cfg(feature = "bincode")]
+pub(crate) fn to_bincode<T: Serialize>(value: &T) -> Result<Vec<u8>, SerError> {
+    use bincode::Options;
+    Ok(bincode_options().serialize(value)?)
+}
+
+#[cfg(feature = "bincode")]
+pub(crate) fn from_bincode<T: DeserializeOwned>(bytes: &[u8]) -> Result<T, SerError> {
+    use bincode::Options;
+    Ok(bincode_options().deserialize(bytes)?)
+}
+
+#[cfg(feature = "bincode")]
+pub(crate) fn to_versioned_bytes(proof: &crate::Proof) -> Result<Vec<u8>, SerError> {
+    use bincode::Options;
+    let mut bytes = Vec::from(PROOF_FORMAT_MAGIC);
+    bytes.extend_from_slice(&PROOF_FORMAT_VERSION.to_le_bytes());
+    bincode_options().serialize_into(&mut bytes, proof)?;
+    Ok(bytes)
+}
+
+#[cfg(feature = "bincode")]
+pub(crate) fn from_versioned_bytes(bytes: &[u8]) -> Result<crate::Proof, SerError> {
+    let encoded = bytes
+        .strip_prefix(&PROOF_FORMAT_MAGIC)
+        .ok_or(SerError::MissingFormatHeader)?;
+    let (version, encoded) = encoded
+        .split_first_chunk::<2>()
+        .ok_or(SerError::MissingFormatHeader)?;
+    match u16::from_le_bytes(*version) {
+        PROOF_FORMAT_VERSION => from_bincode(encoded),
+        found => Err(SerError::UnsupportedProofFormatVersion {
+            found,
+            supported: PROOF_FORMAT_VERSION,
+        }),
+    }
+}
+
+/// Decode a `bincode` encoded proof from `reader`, failing as soon as the [Limits] are exceeded:
+/// the segment count of a composite proof is checked before reading any segment.
+#[cfg(feature = "bincode")]
+pub(crate) fn from_bincode_reader<R: std::io::Read>(
+    mut reader: R,
+    limits: &Limits,
+) -> Result<crate::Proof, SerError> {
+    use bincode::Options;
+    use std::io::Read;
+
+    // The proof starts with the `u32` tag of the inner receipt and, if it's a composite one, with
+    // the `u64` length of its segments.
+    let mut header = [0_u8; 12];
+    reader
+        .read_exact(&mut header[..4])
+        .map_err(bincode::Error::from)?;
+    let mut header_len = 4;
+    if header[..4] == 0_u32.to_le_bytes() {
+        reader
+            .read_exact(&mut header[4..])
+            .map_err(bincode::Error::from)?;
+        header_len = header.len();
+        let count = u64::from_le_bytes(header[4..].try_into().expect("8 bytes"));
+        if count > limits.max_segments as u64 {
+            return Err(SerError::TooManySegments {
+                count,
+                max: limits.max_segments,
+            });
+        }
+    }
+    bincode_options()
+        .with_limit(limits.max_size)
+        .deserialize_from(header[..header_len].chain(reader))
+        .map_err(|e| match *e {
+            bincode::ErrorKind::SizeLimit => SerError::TooLarge {
+                max: limits.max_size,
+            },
+            _ => e.into(),
+        })
+}
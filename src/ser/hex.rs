@@ -0,0 +1,206 @@
+// Copyright 2024, Horizen Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The `serde(with)` modules of the `serde-hex` feature: the digests and the seals are hex
+//! strings in the human readable formats (e.g. JSON), while the binary ones keep the compact
+//! encoding. The human readable deserializers accept both the hex strings and the compact
+//! encoding, so the documents written without the feature can still be read.
+
+use alloc::vec::Vec;
+use core::{fmt, marker::PhantomData};
+
+use risc0_zkp::core::digest::Digest;
+use serde::{
+    de::{self, SeqAccess, Visitor},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+/// A value decoded from its hex string or from the sequence of its `T` items.
+trait FromHex: Sized {
+    type Item: for<'de> Deserialize<'de>;
+
+    const EXPECTING: &'static str;
+
+    fn from_bytes(bytes: Vec<u8>) -> Option<Self>;
+
+    fn from_items(items: Vec<Self::Item>) -> Option<Self>;
+}
+
+impl FromHex for Digest {
+    type Item = u32;
+    const EXPECTING: &'static str = "a hex digest or 8 words";
+
+    fn from_bytes(bytes: Vec<u8>) -> Option<Self> {
+        Digest::try_from(bytes).ok()
+    }
+
+    fn from_items(items: Vec<u32>) -> Option<Self> {
+        Digest::try_from(items).ok()
+    }
+}
+
+impl FromHex for Vec<u32> {
+    type Item = u32;
+    const EXPECTING: &'static str = "a hex seal or a sequence of words";
+
+    fn from_bytes(bytes: Vec<u8>) -> Option<Self> {
+        let (words, []) = bytes.as_chunks::<4>() else {
+            return None;
+        };
+        Some(words.iter().copied().map(u32::from_le_bytes).collect())
+    }
+
+    fn from_items(items: Vec<u32>) -> Option<Self> {
+        Some(items)
+    }
+}
+
+#[cfg(feature = "groth16")]
+impl FromHex for Vec<u8> {
+    type Item = u8;
+    const EXPECTING: &'static str = "a hex seal or a sequence of bytes";
+
+    fn from_bytes(bytes: Vec<u8>) -> Option<Self> {
+        Some(bytes)
+    }
+
+    fn from_items(items: Vec<u8>) -> Option<Self> {
+        Some(items)
+    }
+}
+
+struct HexVisitor<T>(PhantomData<T>);
+
+impl<'de, T: FromHex> Visitor<'de> for HexVisitor<T> {
+    type Value = T;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(T::EXPECTING)
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<T, E> {
+        hex::decode(v)
+            .ok()
+            .and_then(T::from_bytes)
+            .ok_or_else(|| E::invalid_value(de::Unexpected::Str(v), &self))
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<T, A::Error> {
+        let mut items = Vec::with_capacity(seq.size_hint().unwrap_or(0).min(1 << 16));
+        while let Some(item) = seq.next_element()? {
+            items.push(item);
+        }
+        let len = items.len();
+        T::from_items(items).ok_or_else(|| de::Error::invalid_length(len, &self))
+    }
+
+    fn visit_newtype_struct<D: Deserializer<'de>>(self, deserializer: D) -> Result<T, D::Error> {
+        deserializer.deserialize_any(self)
+    }
+}
+
+fn deserialize_hex<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+where
+    T: FromHex + Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    if deserializer.is_human_readable() {
+        deserializer.deserialize_any(HexVisitor(PhantomData))
+    } else {
+        T::deserialize(deserializer)
+    }
+}
+
+/// A [Digest] as a hex string.
+pub(crate) mod digest {
+    use super::*;
+
+    pub(crate) fn serialize<S: Serializer>(digest: &Digest, s: S) -> Result<S::Ok, S::Error> {
+        if s.is_human_readable() {
+            s.serialize_str(&hex::encode(digest.as_bytes()))
+        } else {
+            digest.serialize(s)
+        }
+    }
+
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<Digest, D::Error> {
+        deserialize_hex(d)
+    }
+}
+
+/// A list of [Digest]s as hex strings.
+pub(crate) mod digests {
+    use super::*;
+
+    struct Hex<'a>(&'a Digest);
+
+    impl Serialize for Hex<'_> {
+        fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+            digest::serialize(self.0, s)
+        }
+    }
+
+    struct Unhex(Digest);
+
+    impl<'de> Deserialize<'de> for Unhex {
+        fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+            digest::deserialize(d).map(Self)
+        }
+    }
+
+    pub(crate) fn serialize<S: Serializer>(digests: &[Digest], s: S) -> Result<S::Ok, S::Error> {
+        s.collect_seq(digests.iter().map(Hex))
+    }
+
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<Vec<Digest>, D::Error> {
+        Vec::<Unhex>::deserialize(d).map(|digests| digests.into_iter().map(|d| d.0).collect())
+    }
+}
+
+/// A seal of `u32` words as the hex string of its little endian bytes.
+pub(crate) mod words {
+    use super::*;
+
+    pub(crate) fn serialize<S: Serializer>(words: &[u32], s: S) -> Result<S::Ok, S::Error> {
+        if s.is_human_readable() {
+            let bytes: Vec<u8> = words.iter().flat_map(|w| w.to_le_bytes()).collect();
+            s.serialize_str(&hex::encode(bytes))
+        } else {
+            words.serialize(s)
+        }
+    }
+
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<Vec<u32>, D::Error> {
+        deserialize_hex(d)
+    }
+}
+
+/// A seal of bytes as a hex string.
+#[cfg(feature = "groth16")]
+pub(crate) mod bytes {
+    use super::*;
+
+    pub(crate) fn serialize<S: Serializer>(bytes: &[u8], s: S) -> Result<S::Ok, S::Error> {
+        if s.is_human_readable() {
+            s.serialize_str(&hex::encode(bytes))
+        } else {
+            bytes.serialize(s)
+        }
+    }
+
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<Vec<u8>, D::Error> {
+        deserialize_hex(d)
+    }
+}
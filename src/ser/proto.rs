@@ -0,0 +1,727 @@
+// Copyright 2024, Horizen Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The protobuf encoding of the proofs, described by `proto/proof.proto`.
+//!
+//! The messages are encoded by hand: the fields are written in order, the scalars and the plain
+//! `bytes` fields are omitted when they have the default value, and the unknown fields are skipped
+//! on decoding.
+
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt::Debug;
+
+use risc0_binfmt::{Digestible, ExitCode, SystemState};
+use risc0_zkp::core::digest::Digest;
+use serde::Serialize;
+
+use super::SerError;
+use crate::{
+    receipt::{
+        composite::CompositeReceipt, merkle::MerkleProof, succinct::SuccinctReceipt,
+        InnerAssumptionReceipt, InnerReceipt, Proof,
+    },
+    receipt_claim::{Assumption, Assumptions, MaybePruned, Output, ReceiptClaim, Unknown},
+    segment::SegmentReceipt,
+};
+
+/// The maximum nesting of the composite receipts in the assumptions.
+const MAX_DEPTH: usize = 16;
+
+const VARINT: u64 = 0;
+const I64: u64 = 1;
+const LEN: u64 = 2;
+const I32: u64 = 5;
+
+pub(crate) fn to_proto(proof: &Proof) -> Vec<u8> {
+    let mut w = Writer::default();
+    match &proof.inner {
+        InnerReceipt::Composite(inner) => w.message(1, |w| encode_composite(inner, w)),
+        InnerReceipt::Succinct(inner) => w.message(2, |w| inner.encode_proto(w)),
+        #[cfg(feature = "groth16")]
+        InnerReceipt::Groth16(inner) => w.message(3, |w| inner.encode_proto(w)),
+        #[cfg(feature = "unsafe-fake")]
+        InnerReceipt::Fake(inner) => w.message(4, |w| inner.encode_proto(w)),
+    }
+    w.0
+}
+
+pub(crate) fn from_proto(bytes: &[u8]) -> Result<Proof, SerError> {
+    let mut inner = None;
+    for field in Fields(bytes) {
+        inner = match field? {
+            (1, value) => Some(InnerReceipt::Composite(decode_composite(value.len()?, 0)?)),
+            (2, value) => Some(InnerReceipt::Succinct(Message::decode_proto(value.len()?)?)),
+            #[cfg(feature = "groth16")]
+            (3, value) => Some(InnerReceipt::Groth16(Message::decode_proto(value.len()?)?)),
+            #[cfg(feature = "unsafe-fake")]
+            (4, value) => Some(InnerReceipt::Fake(Message::decode_proto(value.len()?)?)),
+            #[cfg(not(feature = "groth16"))]
+            (3, _) => return Err(invalid("proof: groth16 receipts are not supported")),
+            #[cfg(not(feature = "unsafe-fake"))]
+            (4, _) => return Err(invalid("proof: fake receipts are not supported")),
+            _ => inner,
+        }
+    }
+    inner
+        .map(Proof::new)
+        .ok_or_else(|| invalid("proof: missing receipt"))
+}
+
+fn invalid(what: &str) -> SerError {
+    SerError::Protobuf(format!("invalid {what}"))
+}
+
+#[derive(Default)]
+struct Writer(Vec<u8>);
+
+impl Writer {
+    fn varint(&mut self, mut value: u64) {
+        while value >= 0x80 {
+            self.0.push(value as u8 | 0x80);
+            value >>= 7;
+        }
+        self.0.push(value as u8);
+    }
+
+    fn key(&mut self, field: u32, wire_type: u64) {
+        self.varint(u64::from(field) << 3 | wire_type);
+    }
+
+    fn uint32(&mut self, field: u32, value: u32) {
+        if value != 0 {
+            self.key(field, VARINT);
+            self.varint(value.into());
+        }
+    }
+
+    /// Write a length delimited field, even if empty: a message or a `oneof` arm.
+    fn len(&mut self, field: u32, bytes: &[u8]) {
+        self.key(field, LEN);
+        self.varint(bytes.len() as u64);
+        self.0.extend_from_slice(bytes);
+    }
+
+    fn bytes(&mut self, field: u32, bytes: &[u8]) {
+        if !bytes.is_empty() {
+            self.len(field, bytes);
+        }
+    }
+
+    fn words(&mut self, field: u32, words: &[u32]) {
+        let bytes: Vec<u8> = words.iter().flat_map(|w| w.to_le_bytes()).collect();
+        self.bytes(field, &bytes);
+    }
+
+    fn digest(&mut self, field: u32, digest: &Digest) {
+        self.bytes(field, digest.as_bytes());
+    }
+
+    fn message(&mut self, field: u32, encode: impl FnOnce(&mut Writer)) {
+        let mut inner = Writer::default();
+        encode(&mut inner);
+        self.len(field, &inner.0);
+    }
+}
+
+enum Value<'a> {
+    Varint(u64),
+    Len(&'a [u8]),
+    Fixed,
+}
+
+impl<'a> Value<'a> {
+    fn uint32(self) -> Result<u32, SerError> {
+        match self {
+            Self::Varint(value) => u32::try_from(value).map_err(|_| invalid("uint32")),
+            _ => Err(invalid("wire type: expected a varint")),
+        }
+    }
+
+    fn len(self) -> Result<&'a [u8], SerError> {
+        match self {
+            Self::Len(bytes) => Ok(bytes),
+            _ => Err(invalid("wire type: expected a length delimited field")),
+        }
+    }
+}
+
+/// The fields of an encoded message, in order.
+struct Fields<'a>(&'a [u8]);
+
+impl<'a> Fields<'a> {
+    fn varint(&mut self) -> Result<u64, SerError> {
+        let mut value = 0_u64;
+        for shift in (0..64).step_by(7) {
+            let (&byte, rest) = self.0.split_first().ok_or_else(|| invalid("varint"))?;
+            self.0 = rest;
+            value |= u64::from(byte & 0x7f) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err(invalid("varint"))
+    }
+
+    fn take(&mut self, len: u64) -> Result<&'a [u8], SerError> {
+        let len = usize::try_from(len).map_err(|_| invalid("length"))?;
+        if len > self.0.len() {
+            return Err(invalid("length"));
+        }
+        let (bytes, rest) = self.0.split_at(len);
+        self.0 = rest;
+        Ok(bytes)
+    }
+
+    fn field(&mut self) -> Result<(u32, Value<'a>), SerError> {
+        let key = self.varint()?;
+        let field = u32::try_from(key >> 3).map_err(|_| invalid("field number"))?;
+        let value = match key & 0x7 {
+            VARINT => Value::Varint(self.varint()?),
+            LEN => {
+                let len = self.varint()?;
+                Value::Len(self.take(len)?)
+            }
+            I64 => self.take(8).map(|_| Value::Fixed)?,
+            I32 => self.take(4).map(|_| Value::Fixed)?,
+            _ => return Err(invalid("wire type")),
+        };
+        Ok((field, value))
+    }
+}
+
+impl<'a> Iterator for Fields<'a> {
+    type Item = Result<(u32, Value<'a>), SerError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.0.is_empty() {
+            return None;
+        }
+        let field = self.field();
+        if field.is_err() {
+            self.0 = &[];
+        }
+        Some(field)
+    }
+}
+
+fn digest(bytes: &[u8]) -> Result<Digest, SerError> {
+    Digest::try_from(bytes).map_err(|_| invalid("digest"))
+}
+
+fn words(bytes: &[u8]) -> Result<Vec<u32>, SerError> {
+    let (words, []) = bytes.as_chunks::<4>() else {
+        return Err(invalid("seal: not word aligned"));
+    };
+    Ok(words.iter().copied().map(u32::from_le_bytes).collect())
+}
+
+fn string(bytes: &[u8]) -> Result<String, SerError> {
+    core::str::from_utf8(bytes)
+        .map(ToString::to_string)
+        .map_err(|_| invalid("string"))
+}
+
+trait Message: Sized {
+    fn encode_proto(&self, w: &mut Writer);
+
+    fn decode_proto(bytes: &[u8]) -> Result<Self, SerError>;
+}
+
+impl<T: Message + Clone + Serialize> Message for MaybePruned<T> {
+    fn encode_proto(&self, w: &mut Writer) {
+        match self {
+            Self::Pruned(digest) => w.len(1, digest.as_bytes()),
+            Self::Value(value) => w.message(2, |w| value.encode_proto(w)),
+        }
+    }
+
+    fn decode_proto(bytes: &[u8]) -> Result<Self, SerError> {
+        let mut value = None;
+        for field in Fields(bytes) {
+            value = match field? {
+                (1, value) => Some(Self::Pruned(digest(value.len()?)?)),
+                (2, value) => Some(Self::Value(T::decode_proto(value.len()?)?)),
+                _ => value,
+            }
+        }
+        value.ok_or_else(|| invalid("pruned value: missing"))
+    }
+}
+
+impl Message for Vec<u8> {
+    fn encode_proto(&self, w: &mut Writer) {
+        w.0.extend_from_slice(self);
+    }
+
+    fn decode_proto(bytes: &[u8]) -> Result<Self, SerError> {
+        Ok(bytes.to_vec())
+    }
+}
+
+impl Message for Unknown {
+    fn encode_proto(&self, _w: &mut Writer) {
+        match *self {}
+    }
+
+    fn decode_proto(_bytes: &[u8]) -> Result<Self, SerError> {
+        Err(invalid("assumption receipt: the claim should be pruned"))
+    }
+}
+
+impl Message for SystemState {
+    fn encode_proto(&self, w: &mut Writer) {
+        w.uint32(1, self.pc);
+        w.digest(2, &self.merkle_root);
+    }
+
+    fn decode_proto(bytes: &[u8]) -> Result<Self, SerError> {
+        let (mut pc, mut merkle_root) = (0, &[][..]);
+        for field in Fields(bytes) {
+            match field? {
+                (1, value) => pc = value.uint32()?,
+                (2, value) => merkle_root = value.len()?,
+                _ => {}
+            }
+        }
+        Ok(Self {
+            pc,
+            merkle_root: digest(merkle_root)?,
+        })
+    }
+}
+
+impl Message for ExitCode {
+    fn encode_proto(&self, w: &mut Writer) {
+        let (system, user) = self.into_pair();
+        w.uint32(1, system);
+        w.uint32(2, user);
+    }
+
+    fn decode_proto(bytes: &[u8]) -> Result<Self, SerError> {
+        let (mut system, mut user) = (0, 0);
+        for field in Fields(bytes) {
+            match field? {
+                (1, value) => system = value.uint32()?,
+                (2, value) => user = value.uint32()?,
+                _ => {}
+            }
+        }
+        ExitCode::from_pair(system, user).map_err(|_| invalid("exit code"))
+    }
+}
+
+impl Message for Assumption {
+    fn encode_proto(&self, w: &mut Writer) {
+        w.digest(1, &self.claim);
+        w.digest(2, &self.control_root);
+    }
+
+    fn decode_proto(bytes: &[u8]) -> Result<Self, SerError> {
+        let (mut claim, mut control_root) = (&[][..], &[][..]);
+        for field in Fields(bytes) {
+            match field? {
+                (1, value) => claim = value.len()?,
+                (2, value) => control_root = value.len()?,
+                _ => {}
+            }
+        }
+        Ok(Self {
+            claim: digest(claim)?,
+            control_root: digest(control_root)?,
+        })
+    }
+}
+
+impl Message for Assumptions {
+    fn encode_proto(&self, w: &mut Writer) {
+        for assumption in &self.0 {
+            w.message(1, |w| assumption.encode_proto(w));
+        }
+    }
+
+    fn decode_proto(bytes: &[u8]) -> Result<Self, SerError> {
+        let mut assumptions = Vec::new();
+        for field in Fields(bytes) {
+            if let (1, value) = field? {
+                assumptions.push(Message::decode_proto(value.len()?)?);
+            }
+        }
+        Ok(Self(assumptions))
+    }
+}
+
+impl Message for Output {
+    fn encode_proto(&self, w: &mut Writer) {
+        w.message(1, |w| self.journal.encode_proto(w));
+        w.message(2, |w| self.assumptions.encode_proto(w));
+    }
+
+    fn decode_proto(bytes: &[u8]) -> Result<Self, SerError> {
+        let (mut journal, mut assumptions) = (None, None);
+        for field in Fields(bytes) {
+            match field? {
+                (1, value) => journal = Some(Message::decode_proto(value.len()?)?),
+                (2, value) => assumptions = Some(Message::decode_proto(value.len()?)?),
+                _ => {}
+            }
+        }
+        Ok(Self {
+            journal: journal.ok_or_else(|| invalid("output: missing journal"))?,
+            assumptions: assumptions.ok_or_else(|| invalid("output: missing assumptions"))?,
+        })
+    }
+}
+
+impl Message for ReceiptClaim {
+    fn encode_proto(&self, w: &mut Writer) {
+        w.message(1, |w| self.pre.encode_proto(w));
+        w.message(2, |w| self.post.encode_proto(w));
+        w.message(3, |w| self.exit_code.encode_proto(w));
+        match &self.input {
+            MaybePruned::Pruned(digest) => w.digest(4, digest),
+            MaybePruned::Value(None) => {}
+            MaybePruned::Value(Some(input)) => match input.x {},
+        }
+        match &self.output {
+            MaybePruned::Pruned(digest) => w.message(5, |w| w.len(1, digest.as_bytes())),
+            MaybePruned::Value(None) => {}
+            MaybePruned::Value(Some(output)) => {
+                w.message(5, |w| w.message(2, |w| output.encode_proto(w)))
+            }
+        }
+    }
+
+    fn decode_proto(bytes: &[u8]) -> Result<Self, SerError> {
+        let (mut pre, mut post, mut exit_code) = (None, None, None);
+        let (mut input, mut output) = (MaybePruned::Value(None), MaybePruned::Value(None));
+        for field in Fields(bytes) {
+            match field? {
+                (1, value) => pre = Some(Message::decode_proto(value.len()?)?),
+                (2, value) => post = Some(Message::decode_proto(value.len()?)?),
+                (3, value) => exit_code = Some(Message::decode_proto(value.len()?)?),
+                (4, value) => input = MaybePruned::Pruned(digest(value.len()?)?),
+                (5, value) => {
+                    output = match MaybePruned::<Output>::decode_proto(value.len()?)? {
+                        MaybePruned::Value(output) => MaybePruned::Value(Some(output)),
+                        MaybePruned::Pruned(digest) => MaybePruned::Pruned(digest),
+                    }
+                }
+                _ => {}
+            }
+        }
+        Ok(Self {
+            pre: pre.ok_or_else(|| invalid("claim: missing pre state"))?,
+            post: post.ok_or_else(|| invalid("claim: missing post state"))?,
+            exit_code: exit_code.ok_or_else(|| invalid("claim: missing exit code"))?,
+            input,
+            output,
+        })
+    }
+}
+
+impl Message for MerkleProof {
+    fn encode_proto(&self, w: &mut Writer) {
+        w.uint32(1, self.index);
+        for d in &self.digests {
+            w.len(2, d.as_bytes());
+        }
+    }
+
+    fn decode_proto(bytes: &[u8]) -> Result<Self, SerError> {
+        let (mut index, mut digests) = (0, Vec::new());
+        for field in Fields(bytes) {
+            match field? {
+                (1, value) => index = value.uint32()?,
+                (2, value) => digests.push(digest(value.len()?)?),
+                _ => {}
+            }
+        }
+        Ok(Self { index, digests })
+    }
+}
+
+impl Message for SegmentReceipt {
+    fn encode_proto(&self, w: &mut Writer) {
+        w.words(1, &self.seal);
+        w.uint32(2, self.index);
+        w.bytes(3, self.hashfn.as_bytes());
+        w.digest(4, &self.verifier_parameters);
+        w.message(5, |w| self.claim.encode_proto(w));
+    }
+
+    fn decode_proto(bytes: &[u8]) -> Result<Self, SerError> {
+        let (mut seal, mut index, mut hashfn) = (&[][..], 0, &[][..]);
+        let (mut verifier_parameters, mut claim) = (&[][..], None);
+        for field in Fields(bytes) {
+            match field? {
+                (1, value) => seal = value.len()?,
+                (2, value) => index = value.uint32()?,
+                (3, value) => hashfn = value.len()?,
+                (4, value) => verifier_parameters = value.len()?,
+                (5, value) => claim = Some(Message::decode_proto(value.len()?)?),
+                _ => {}
+            }
+        }
+        Ok(Self {
+            seal: words(seal)?,
+            index,
+            hashfn: string(hashfn)?,
+            verifier_parameters: digest(verifier_parameters)?,
+            claim: claim.ok_or_else(|| invalid("segment receipt: missing claim"))?,
+        })
+    }
+}
+
+impl<C> Message for SuccinctReceipt<C>
+where
+    C: Message + Digestible + Debug + Clone + Serialize,
+{
+    fn encode_proto(&self, w: &mut Writer) {
+        w.words(1, &self.seal);
+        w.digest(2, &self.control_id);
+        w.message(3, |w| self.claim.encode_proto(w));
+        w.bytes(4, self.hashfn.as_bytes());
+        w.digest(5, &self.verifier_parameters);
+        w.message(6, |w| self.control_inclusion_proof.encode_proto(w));
+    }
+
+    fn decode_proto(bytes: &[u8]) -> Result<Self, SerError> {
+        let (mut seal, mut control_id, mut claim) = (&[][..], &[][..], None);
+        let (mut hashfn, mut verifier_parameters, mut control_inclusion_proof) =
+            (&[][..], &[][..], None);
+        for field in Fields(bytes) {
+            match field? {
+                (1, value) => seal = value.len()?,
+                (2, value) => control_id = value.len()?,
+                (3, value) => claim = Some(Message::decode_proto(value.len()?)?),
+                (4, value) => hashfn = value.len()?,
+                (5, value) => verifier_parameters = value.len()?,
+                (6, value) => control_inclusion_proof = Some(Message::decode_proto(value.len()?)?),
+                _ => {}
+            }
+        }
+        Ok(Self {
+            seal: words(seal)?,
+            control_id: digest(control_id)?,
+            claim: claim.ok_or_else(|| invalid("succinct receipt: missing claim"))?,
+            hashfn: string(hashfn)?,
+            verifier_parameters: digest(verifier_parameters)?,
+            control_inclusion_proof: control_inclusion_proof
+                .ok_or_else(|| invalid("succinct receipt: missing control inclusion proof"))?,
+        })
+    }
+}
+
+#[cfg(feature = "groth16")]
+impl<C> Message for crate::receipt::groth16::Groth16Receipt<C>
+where
+    C: Message + Digestible + Debug + Clone + Serialize,
+{
+    fn encode_proto(&self, w: &mut Writer) {
+        w.bytes(1, &self.seal);
+        w.message(2, |w| self.claim.encode_proto(w));
+        w.digest(3, &self.verifier_parameters);
+    }
+
+    fn decode_proto(bytes: &[u8]) -> Result<Self, SerError> {
+        let (mut seal, mut claim, mut verifier_parameters) = (&[][..], None, &[][..]);
+        for field in Fields(bytes) {
+            match field? {
+                (1, value) => seal = value.len()?,
+                (2, value) => claim = Some(Message::decode_proto(value.len()?)?),
+                (3, value) => verifier_parameters = value.len()?,
+                _ => {}
+            }
+        }
+        Ok(Self::new(
+            seal.to_vec(),
+            claim.ok_or_else(|| invalid("groth16 receipt: missing claim"))?,
+            digest(verifier_parameters)?,
+        ))
+    }
+}
+
+#[cfg(feature = "unsafe-fake")]
+impl<C> Message for crate::receipt::fake::FakeReceipt<C>
+where
+    C: Message + Digestible + Debug + Clone + Serialize,
+{
+    fn encode_proto(&self, w: &mut Writer) {
+        w.message(1, |w| self.claim.encode_proto(w));
+    }
+
+    fn decode_proto(bytes: &[u8]) -> Result<Self, SerError> {
+        let mut claim = None;
+        for field in Fields(bytes) {
+            if let (1, value) = field? {
+                claim = Some(MaybePruned::<C>::decode_proto(value.len()?)?);
+            }
+        }
+        Ok(Self::new(
+            claim.ok_or_else(|| invalid("fake receipt: missing claim"))?,
+        ))
+    }
+}
+
+fn encode_composite(receipt: &CompositeReceipt, w: &mut Writer) {
+    for segment in &receipt.segments {
+        w.message(1, |w| segment.encode_proto(w));
+    }
+    for assumption in &receipt.assumption_receipts {
+        w.message(2, |w| encode_assumption(assumption, w));
+    }
+    w.digest(3, &receipt.verifier_parameters);
+}
+
+fn decode_composite(bytes: &[u8], depth: usize) -> Result<CompositeReceipt, SerError> {
+    if depth > MAX_DEPTH {
+        return Err(invalid("composite receipt: assumptions nested too deep"));
+    }
+    let (mut segments, mut assumption_receipts) = (Vec::new(), Vec::new());
+    let mut verifier_parameters = &[][..];
+    for field in Fields(bytes) {
+        match field? {
+            (1, value) => segments.push(Message::decode_proto(value.len()?)?),
+            (2, value) => assumption_receipts.push(decode_assumption(value.len()?, depth + 1)?),
+            (3, value) => verifier_parameters = value.len()?,
+            _ => {}
+        }
+    }
+    Ok(CompositeReceipt {
+        segments,
+        assumption_receipts,
+        verifier_parameters: digest(verifier_parameters)?,
+    })
+}
+
+fn encode_assumption(receipt: &InnerAssumptionReceipt, w: &mut Writer) {
+    match receipt {
+        InnerAssumptionReceipt::Composite(inner) => w.message(1, |w| encode_composite(inner, w)),
+        InnerAssumptionReceipt::Succinct(inner) => w.message(2, |w| inner.encode_proto(w)),
+        #[cfg(feature = "groth16")]
+        InnerAssumptionReceipt::Groth16(inner) => w.message(3, |w| inner.encode_proto(w)),
+        #[cfg(feature = "unsafe-fake")]
+        InnerAssumptionReceipt::Fake(inner) => w.message(4, |w| inner.encode_proto(w)),
+    }
+}
+
+fn decode_assumption(bytes: &[u8], depth: usize) -> Result<InnerAssumptionReceipt, SerError> {
+    let mut inner = None;
+    for field in Fields(bytes) {
+        inner = match field? {
+            (1, value) => Some(InnerAssumptionReceipt::Composite(decode_composite(
+                value.len()?,
+                depth,
+            )?)),
+            (2, value) => Some(InnerAssumptionReceipt::Succinct(Message::decode_proto(
+                value.len()?,
+            )?)),
+            #[cfg(feature = "groth16")]
+            (3, value) => Some(InnerAssumptionReceipt::Groth16(Message::decode_proto(
+                value.len()?,
+            )?)),
+            #[cfg(feature = "unsafe-fake")]
+            (4, value) => Some(InnerAssumptionReceipt::Fake(Message::decode_proto(
+                value.len()?,
+            )?)),
+            #[cfg(not(feature = "groth16"))]
+            (3, _) => return Err(invalid("assumption receipt: groth16 is not supported")),
+            #[cfg(not(feature = "unsafe-fake"))]
+            (4, _) => return Err(invalid("assumption receipt: fake is not supported")),
+            _ => inner,
+        }
+    }
+    inner.ok_or_else(|| invalid("assumption receipt: missing receipt"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    fn write(encode: impl FnOnce(&mut Writer)) -> Vec<u8> {
+        let mut w = Writer::default();
+        encode(&mut w);
+        w.0
+    }
+
+    #[test]
+    fn encode_the_wire_format() {
+        assert_eq!(write(|w| w.uint32(1, 150)), [0x08, 0x96, 0x01]);
+        assert_eq!(write(|w| w.uint32(1, 0)), [0; 0]);
+        assert_eq!(write(|w| w.bytes(2, b"ab")), [0x12, 2, b'a', b'b']);
+        assert_eq!(write(|w| w.len(2, &[])), [0x12, 0]);
+        assert_eq!(write(|w| w.words(3, &[1])), [0x1a, 4, 1, 0, 0, 0]);
+    }
+
+    #[test]
+    fn skip_the_unknown_fields() {
+        let state = SystemState {
+            pc: 4,
+            merkle_root: Digest::from([7; 8]),
+        };
+        let mut bytes = write(|w| state.encode_proto(w));
+        bytes.extend([0x19, 0, 0, 0, 0, 0, 0, 0, 0]);
+        bytes.extend([0x25, 0, 0, 0, 0]);
+        bytes.extend(write(|w| w.uint32(9, 1)));
+
+        assert_eq!(SystemState::decode_proto(&bytes).unwrap(), state);
+    }
+
+    #[test]
+    fn reject_the_malformed_messages() {
+        assert!(SystemState::decode_proto(&[0x08]).is_err());
+        assert!(SystemState::decode_proto(&[0x12, 5, 0]).is_err());
+        assert!(SystemState::decode_proto(&[0x0b]).is_err());
+        assert!(SystemState::decode_proto(&write(|w| w.uint32(1, 4))).is_err());
+        assert!(words(&[1, 2, 3]).is_err());
+        assert!(MaybePruned::<Unknown>::decode_proto(&write(|w| w.len(2, &[]))).is_err());
+    }
+
+    #[test]
+    fn keep_the_empty_values() {
+        let journal = MaybePruned::Value(vec![]);
+        let bytes = write(|w| journal.encode_proto(w));
+
+        assert_eq!(
+            MaybePruned::<Vec<u8>>::decode_proto(&bytes).unwrap(),
+            journal
+        );
+    }
+
+    #[test]
+    fn bound_the_assumptions_nesting() {
+        let leaf = CompositeReceipt {
+            segments: vec![],
+            assumption_receipts: vec![],
+            verifier_parameters: Digest::ZERO,
+        };
+        let nested = |depth| {
+            (0..depth).fold(leaf.clone(), |inner, _| CompositeReceipt {
+                assumption_receipts: vec![InnerAssumptionReceipt::Composite(inner)],
+                ..leaf.clone()
+            })
+        };
+
+        let bytes = write(|w| encode_composite(&nested(MAX_DEPTH), w));
+        assert_eq!(decode_composite(&bytes, 0).unwrap(), nested(MAX_DEPTH));
+        let bytes = write(|w| encode_composite(&nested(MAX_DEPTH + 1), w));
+        assert!(decode_composite(&bytes, 0).is_err());
+    }
+}
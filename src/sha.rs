@@ -49,6 +49,17 @@
 //! // Hashing with bytes or words should not change the result
 //! assert_eq!(hash_hash, hash_hash_words);
 //! ```
+//!
+//! # Hardware acceleration
+//!
+//! Outside the guest, [Impl] hashes with the `sha2` crate, that detects the SHA extensions at
+//! runtime (SHA-NI on `x86`/`x86_64`, the ARMv8 SHA2 instructions on `aarch64`) and falls back
+//! to the portable implementation on the other CPUs: the journal digests and the Merkle proofs
+//! are accelerated without any configuration. The `sha-soft` feature forces the portable
+//! implementation, e.g. to measure the speedup with `cargo bench --bench base journal`.
+
+use alloc::string::String;
+use core::fmt;
 
 pub use risc0_zkp::core::{digest::Digest, hash::sha::Sha256};
 
@@ -68,3 +79,118 @@ impl<D: ?Sized + risc0_binfmt::Digestible> Digestible for D {
         self.digest::<Impl>()
     }
 }
+
+/// Error returned by [digest_from_hex].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DigestParseError {
+    /// The string doesn't contain 64 hex digits: the number of digits found.
+    InvalidLength(usize),
+    /// The string contains a character that is not an hex digit.
+    InvalidHex,
+}
+
+impl fmt::Display for DigestParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::InvalidLength(len) => {
+                write!(f, "invalid digest: expected 64 hex digits, found {len}")
+            }
+            Self::InvalidHex => write!(f, "invalid digest: not an hex string"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DigestParseError {}
+
+/// Parse a [Digest] from its hex representation, with or without the `0x` prefix. The bytes are
+/// taken in the given order, the same used by the [Digest] `Display` and the `digest!` macro: no
+/// word is byte swapped.
+pub fn digest_from_hex(s: &str) -> Result<Digest, DigestParseError> {
+    let digits = s.strip_prefix("0x").unwrap_or(s);
+    let mut bytes = [0_u8; risc0_zkp::core::digest::DIGEST_BYTES];
+    if digits.len() != 2 * bytes.len() {
+        return Err(DigestParseError::InvalidLength(digits.len()));
+    }
+    hex::decode_to_slice(digits, &mut bytes).map_err(|_| DigestParseError::InvalidHex)?;
+    Ok(bytes.into())
+}
+
+/// The hex representation of a [Digest], without the `0x` prefix: the inverse of
+/// [digest_from_hex].
+pub fn digest_to_hex(digest: &Digest) -> String {
+    hex::encode(digest.as_bytes())
+}
+
+/// Compare two digests in the verification path. With the `constant-time` feature the comparison
+/// takes the same time wherever the digests differ.
+pub(crate) fn digest_eq(a: &Digest, b: &Digest) -> bool {
+    #[cfg(feature = "constant-time")]
+    {
+        use subtle::ConstantTimeEq;
+        a.as_bytes().ct_eq(b.as_bytes()).into()
+    }
+    #[cfg(not(feature = "constant-time"))]
+    {
+        a == b
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use risc0_zkp::core::digest::digest;
+    use rstest::rstest;
+
+    const HEX: &str = "9db9988d9fbcacadf2bd29fc7c60b98bc4234342fe536eb983169eb6cc248009";
+
+    #[rstest]
+    #[case::plain(HEX)]
+    #[case::prefixed("0x9db9988d9fbcacadf2bd29fc7c60b98bc4234342fe536eb983169eb6cc248009")]
+    #[case::uppercase("9DB9988D9FBCACADF2BD29FC7C60B98BC4234342FE536EB983169EB6CC248009")]
+    fn parse_digest_from_hex(#[case] s: &str) {
+        let digest = digest_from_hex(s).unwrap();
+
+        assert_eq!(
+            digest,
+            digest!("9db9988d9fbcacadf2bd29fc7c60b98bc4234342fe536eb983169eb6cc248009")
+        );
+        assert_eq!(digest.as_words()[0], 0x8d98b99d);
+        assert_eq!(digest_to_hex(&digest), HEX);
+    }
+
+    #[rstest]
+    #[case::empty("", DigestParseError::InvalidLength(0))]
+    #[case::only_prefix("0x", DigestParseError::InvalidLength(0))]
+    #[case::short(&HEX[2..], DigestParseError::InvalidLength(62))]
+    #[case::long(
+        "9db9988d9fbcacadf2bd29fc7c60b98bc4234342fe536eb983169eb6cc24800900",
+        DigestParseError::InvalidLength(66)
+    )]
+    #[case::not_hex(
+        "zdb9988d9fbcacadf2bd29fc7c60b98bc4234342fe536eb983169eb6cc248009",
+        DigestParseError::InvalidHex
+    )]
+    fn reject_invalid_hex_digest(#[case] s: &str, #[case] expected: DigestParseError) {
+        assert_eq!(digest_from_hex(s), Err(expected));
+    }
+
+    #[rstest]
+    #[case::equal(HEX, HEX, true)]
+    #[case::first_byte(
+        HEX,
+        "8db9988d9fbcacadf2bd29fc7c60b98bc4234342fe536eb983169eb6cc248009",
+        false
+    )]
+    #[case::last_byte(
+        HEX,
+        "9db9988d9fbcacadf2bd29fc7c60b98bc4234342fe536eb983169eb6cc248008",
+        false
+    )]
+    fn compare_digests(#[case] a: &str, #[case] b: &str, #[case] expected: bool) {
+        let (a, b) = (digest_from_hex(a).unwrap(), digest_from_hex(b).unwrap());
+
+        assert_eq!(digest_eq(&a, &b), expected);
+        assert_eq!(digest_eq(&b, &a), expected);
+    }
+}
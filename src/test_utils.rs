@@ -0,0 +1,40 @@
+// Copyright Copyright 2024, Horizen Labs, Inc.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! Fixtures shared by the unit tests of the foreign interfaces.
+
+use serde::Deserialize;
+
+use crate::{Journal, Proof, Vk};
+
+/// A proof case of the integration tests: the receipt is stored in `receipt_path`.
+#[derive(Deserialize)]
+pub(crate) struct Case {
+    receipt_path: std::path::PathBuf,
+    pub(crate) journal: Journal,
+    pub(crate) vk: Vk,
+}
+
+/// The `bincode` encoded 1.2 succinct proof, alongside its case.
+pub(crate) fn succinct_case() -> (std::vec::Vec<u8>, Case) {
+    let file =
+        std::fs::File::open("./resources/cases/prover_1.2.0/vm_1.2.0/succinct_16.json").unwrap();
+    let case: Case = serde_json::from_reader(file).unwrap();
+    let proof: Proof =
+        ciborium::from_reader(std::fs::File::open(&case.receipt_path).unwrap()).unwrap();
+    (proof.to_bincode().unwrap(), case)
+}
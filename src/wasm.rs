@@ -0,0 +1,88 @@
+// Copyright Copyright 2024, Horizen Labs, Inc.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+//! A self-contained verification entry point for JavaScript, exported with `wasm-bindgen`.
+//!
+//! The proof is passed as the `bincode` encoding of a [Proof] (see [`Proof::to_bincode`]) and
+//! it's always verified with the risc0 `1.2` [VerifierContext]. Failures are reported as
+//! readable strings, so the caller doesn't need to know the Rust error types.
+
+use alloc::{
+    format,
+    string::{String, ToString},
+};
+
+use wasm_bindgen::prelude::*;
+
+use crate::{Digest, Digestible, Journal, Proof, VerifierContext};
+
+/// Verify the `bincode` encoded `proof_bytes` against the 32 bytes `image_id` and the raw
+/// `journal` bytes.
+///
+/// On failure the JavaScript caller gets an exception carrying a message that describes it.
+#[wasm_bindgen]
+pub fn verify_wasm(proof_bytes: &[u8], image_id: &[u8], journal: &[u8]) -> Result<(), JsValue> {
+    verify_bytes(proof_bytes, image_id, journal).map_err(|e| JsValue::from_str(&e))
+}
+
+fn verify_bytes(proof_bytes: &[u8], image_id: &[u8], journal: &[u8]) -> Result<(), String> {
+    let proof = Proof::from_bincode(proof_bytes).map_err(|e| format!("invalid proof: {e}"))?;
+    let image_id = Digest::try_from(image_id).map_err(|_| {
+        format!(
+            "invalid image id: expected 32 bytes, got {}",
+            image_id.len()
+        )
+    })?;
+    let journal = Journal::new(journal.to_vec());
+    proof
+        .verify(&VerifierContext::v1_2(), image_id, journal.digest())
+        .map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::succinct_case;
+
+    #[test]
+    fn should_verify_a_valid_proof() {
+        let (bytes, case) = succinct_case();
+
+        assert_eq!(
+            verify_bytes(&bytes, case.vk.as_bytes(), &case.journal.bytes),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn should_describe_the_failures() {
+        let (bytes, case) = succinct_case();
+
+        assert!(
+            verify_bytes(&bytes[1..], case.vk.as_bytes(), &case.journal.bytes)
+                .unwrap_err()
+                .starts_with("invalid proof: ")
+        );
+        assert_eq!(
+            verify_bytes(&bytes, &case.vk.as_bytes()[1..], &case.journal.bytes),
+            Err("invalid image id: expected 32 bytes, got 31".to_string())
+        );
+        assert!(verify_bytes(&bytes, case.vk.as_bytes(), &[])
+            .unwrap_err()
+            .starts_with("claim digest does not match"));
+    }
+}
@@ -13,11 +13,11 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use risc0_verifier::verify;
 use risc0_verifier::Digestible as _;
+use risc0_verifier::{verify, verify_batch};
 use risc0_verifier::{
-    CircuitCoreDef, CompositeReceipt, Journal, MaybePruned, Proof, ReceiptClaim, SuccinctReceipt,
-    VerifierContext, Vk,
+    CircuitCoreDef, CompositeReceipt, Digest, InnerReceipt, Journal, MaybePruned, Proof,
+    ReceiptClaim, ReceiptKind, SuccinctReceipt, VerifierContext, VerifierVersion, Vk,
 };
 use risc0_zkp::core::hash::HashFn;
 use risc0_zkp::field::baby_bear::BabyBear;
@@ -59,8 +59,8 @@ mod legacy {
         let Data { vk, proof, pubs } =
             serde_json::from_reader(std::fs::File::open(path).unwrap()).unwrap();
 
-        let proof = <Vec<u8>>::try_from(hex::decode(proof).unwrap()).unwrap();
-        let pubs = <Vec<u8>>::try_from(hex::decode(pubs).unwrap()).unwrap();
+        let proof = hex::decode(proof).unwrap();
+        let pubs = hex::decode(pubs).unwrap();
 
         (vk, proof, pubs)
     }
@@ -94,6 +94,231 @@ fn verify_valid_proof() {
     verify(&VerifierContext::v1_0(), case.vk, proof, case.journal).unwrap()
 }
 
+#[test]
+fn decode_journal_after_verification() {
+    let case: Case = read_all("./resources/cases/prover_1.2.0/vm_1.2.0/sha_16.json").unwrap();
+    let proof = case.get_proof().unwrap();
+
+    proof
+        .verify(&VerifierContext::v1_2(), case.vk, case.journal.digest())
+        .unwrap();
+
+    // The guest commits the number of executed loops as `u64`.
+    let count: u64 = case.journal.decode().unwrap();
+    assert_eq!(count, 1409);
+    assert_eq!(
+        case.journal.decode::<u32>(),
+        Err(risc0_verifier::JournalDecodeError::TrailingBytes)
+    );
+}
+
+#[test]
+fn verify_batch_reports_every_item_in_order() {
+    let case: Case = read_all("./resources/cases/prover_1.2.0/vm_1.2.0/sha_16.json").unwrap();
+    let proof = case.get_proof().unwrap();
+    let image_id = case.vk.0;
+    let pubs = case.journal.digest();
+    let mut wrong_pubs = pubs;
+    wrong_pubs.as_mut_words()[0] ^= 1;
+
+    let items = [
+        (proof.clone(), image_id, pubs),
+        (proof.clone(), image_id, wrong_pubs),
+        (proof, image_id, pubs),
+    ];
+
+    let results = verify_batch(&VerifierContext::v1_2(), &items);
+
+    assert_eq!(results.len(), items.len());
+    assert_eq!(results[0], Ok(()));
+    assert!(matches!(
+        results[1],
+        Err(VerificationError::ClaimDigestMismatch { .. })
+    ));
+    assert_eq!(results[2], Ok(()));
+}
+
+#[rstest]
+#[case::fail_fast(risc0_verifier::StopPolicy::FailFast, 2)]
+#[case::continue_all(risc0_verifier::StopPolicy::ContinueAll, 3)]
+fn verify_stream_follows_the_stop_policy(
+    #[case] policy: risc0_verifier::StopPolicy,
+    #[case] pulled: usize,
+) {
+    let case: Case = read_all("./resources/cases/prover_1.2.0/vm_1.2.0/sha_16.json").unwrap();
+    let proof = case.get_proof().unwrap();
+    let image_id = case.vk.0;
+    let pubs = case.journal.digest();
+    let mut wrong_pubs = pubs;
+    wrong_pubs.as_mut_words()[0] ^= 1;
+    let items = [
+        (proof.clone(), image_id, pubs),
+        (proof.clone(), image_id, wrong_pubs),
+        (proof, image_id, pubs),
+    ];
+    let count = std::cell::Cell::new(0);
+    let ctx = VerifierContext::v1_2();
+
+    let results: Vec<_> = risc0_verifier::verify_stream(
+        &ctx,
+        items.into_iter().inspect(|_| count.set(count.get() + 1)),
+        policy,
+    )
+    .collect();
+
+    assert_eq!(count.get(), pulled);
+    assert_eq!(results.len(), pulled);
+    assert_eq!(results[0], Ok(()));
+    assert!(matches!(
+        results[1],
+        Err(VerificationError::ClaimDigestMismatch { .. })
+    ));
+}
+
+#[rstest]
+#[case::v1_0(VerifierContext::v1_0().with_max_po2(13).unwrap(), VerifierContext::v1_0().with_max_po2(24).unwrap(), "./resources/cases/prover_1.0.3/vm_1.0.5/sha_16.json")]
+#[case::v1_1(VerifierContext::v1_1().with_max_po2(13).unwrap(), VerifierContext::v1_1().with_max_po2(24).unwrap(), "./resources/cases/prover_1.1.3/vm_1.1.3/sha_16.json")]
+#[case::v1_2(VerifierContext::v1_2().with_max_po2(13).unwrap(), VerifierContext::v1_2().with_max_po2(24).unwrap(), "./resources/cases/prover_1.2.0/vm_1.2.0/sha_16.json")]
+fn max_po2_limits_the_segment_size<SC: CircuitCoreDef, RC: CircuitCoreDef>(
+    #[case] strict: VerifierContext<SC, RC>,
+    #[case] relaxed: VerifierContext<SC, RC>,
+    #[case] path: &str,
+) {
+    let case: Case = read_all(path).unwrap();
+    let proof = case.get_proof().unwrap();
+
+    // The first segment is the bigger one: the seals are not verified.
+    let segment = &proof.inner.composite().unwrap().segments[0];
+    assert!(segment.po2::<SC>().unwrap() > 13);
+    let control_id = segment.control_id(&strict).unwrap();
+    assert_eq!(
+        proof.verify(&strict, case.vk.0, case.journal.digest()),
+        Err(VerificationError::ControlVerificationError { control_id })
+    );
+    assert_eq!(
+        proof
+            .verify_with_trace(&strict, case.vk.0, case.journal.digest())
+            .0,
+        Err(VerificationError::ControlVerificationError { control_id })
+    );
+    assert!(matches!(
+        proof.verify_and_extract(&strict, case.vk.0, case.journal.digest()),
+        Err(risc0_verifier::Error::SegmentPo2TooLarge { po2, max: 13, .. }) if po2 > 13
+    ));
+    assert!(matches!(
+        proof.verify_any(&strict, &[case.vk.0], case.journal.digest()),
+        Err(risc0_verifier::Error::SegmentPo2TooLarge { po2, max: 13, .. }) if po2 > 13
+    ));
+    proof.check_po2(&relaxed).unwrap();
+    proof
+        .verify(&relaxed, case.vk.0, case.journal.digest())
+        .unwrap();
+}
+
+#[rstest]
+#[case::v1_0(VerifierContext::v1_0())]
+#[case::v1_1(VerifierContext::v1_1())]
+#[case::v1_2(VerifierContext::v1_2())]
+fn exposes_the_circuit_info<SC: CircuitCoreDef, RC: CircuitCoreDef>(
+    #[case] ctx: VerifierContext<SC, RC>,
+) {
+    use risc0_verifier::ProtocolInfo;
+
+    assert_eq!(ctx.circuit_info(), ProtocolInfo(*b"RV32IM:rev1v1___"));
+    assert_eq!(
+        ctx.recursive_circuit_info(),
+        ProtocolInfo(*b"RECURSION:rev1v1")
+    );
+    assert_eq!(ctx.circuit_output_size(), 138);
+    assert_eq!(ctx.circuit_mix_size(), 40);
+}
+
+#[rstest]
+fn reject_max_po2_out_of_range(#[values(0, 12, 25)] po2: usize) {
+    assert_eq!(
+        VerifierContext::v1_2().with_max_po2(po2).err(),
+        Some(risc0_verifier::Error::Po2OutOfRange {
+            po2,
+            min: 13,
+            max: 24
+        })
+    );
+}
+
+#[rstest]
+#[case::v1_0(
+    VerifierContext::v1_0(),
+    "a516a057c9fbf5629106300934d48e0e775d4230e41e503347cad96fcbde7e2e"
+)]
+#[case::v1_1(
+    VerifierContext::v1_1(),
+    "8b6dcf11d463ac455361b41fb3ed053febb817491bdea00fdb340e45013b852e"
+)]
+#[case::v1_2(
+    VerifierContext::v1_2(),
+    "8cdad9242664be3112aba377c5425a4df735eb1c6966472b561d2855932c0469"
+)]
+fn control_root_is_the_published_one<SC: CircuitCoreDef, RC: CircuitCoreDef>(
+    #[case] ctx: VerifierContext<SC, RC>,
+    #[case] expected: &str,
+) {
+    assert_eq!(
+        ctx.control_root().map(hex::encode),
+        Some(expected.to_owned())
+    );
+}
+
+#[rstest]
+#[case::v1_0(VerifierContext::v1_0(), VerifierVersion::V1_0, "risc0-v1.0")]
+#[case::v1_1(VerifierContext::v1_1(), VerifierVersion::V1_1, "risc0-v1.1")]
+#[case::v1_2(VerifierContext::v1_2(), VerifierVersion::V1_2, "risc0-v1.2")]
+fn context_is_named_after_its_version<SC: CircuitCoreDef, RC: CircuitCoreDef>(
+    #[case] ctx: VerifierContext<SC, RC>,
+    #[case] version: VerifierVersion,
+    #[case] name: &str,
+) {
+    assert_eq!(ctx.version(), Some(version));
+    assert_eq!(ctx.name(), name);
+    assert_eq!(ctx.to_string(), name);
+    assert_eq!(version.to_string(), name);
+}
+
+#[test]
+fn custom_context_has_no_version() {
+    let mut ctx = VerifierContext::v1_2();
+    ctx.succinct_verifier_parameters = None;
+
+    assert_eq!(ctx.version(), None);
+    assert_eq!(ctx.to_string(), "risc0-custom");
+}
+
+#[cfg(feature = "v1_3")]
+#[rstest]
+#[case::succinct("./resources/cases/prover_1.2.0/vm_1.2.0/succinct_22.json")]
+#[case::composite("./resources/cases/prover_1.2.0/vm_1.2.0/sha_16.json")]
+fn v1_3_scaffold_verifies_no_proof(#[case] path: &str) {
+    let ctx = VerifierContext::v1_3();
+    let case: Case = read_all(path).unwrap();
+    let proof = case.get_proof().unwrap();
+
+    assert_eq!(ctx.version(), None);
+    assert!(proof.verify(&ctx, case.vk, case.journal.digest()).is_err());
+}
+
+#[cfg(feature = "v1_3")]
+#[test]
+fn v1_3_scaffold_trusts_no_control_id() {
+    use risc0_verifier::circuit::{v1_3, HashFn};
+
+    for hash in HashFn::ALL {
+        assert_eq!(v1_3::control_id_by_fn(hash, 16), None);
+        assert_eq!(v1_3::control_ids(hash).count(), 0);
+    }
+    assert_eq!(v1_3::identify_control_id(Digest::ZERO), None);
+    let params = VerifierContext::v1_3().segment_verifier_parameters.unwrap();
+    assert!(params.control_ids.is_empty());
+}
+
 mod v1_0 {
     use super::*;
 
@@ -101,6 +326,8 @@ mod v1_0 {
     #[case::should_pass(VerifierContext::v1_0())]
     #[should_panic(expected = "control_id mismatch")]
     #[case::should_fails_with_new_verifier(VerifierContext::v1_1())]
+    #[should_panic(expected = "control_id mismatch")]
+    #[case::should_fails_with_newest_verifier(VerifierContext::v1_2())]
     fn verify_valid_proof<SC: CircuitCoreDef, RC: CircuitCoreDef>(
         #[case] ctx: VerifierContext<SC, RC>,
         #[files("./resources/cases/prover_1.0.*/**/*.json")] path: PathBuf,
@@ -111,6 +338,21 @@ mod v1_0 {
 
         proof.verify(&ctx, case.vk, case.journal.digest()).unwrap()
     }
+
+    #[rstest]
+    fn verify_auto_detects_the_version(
+        #[files("./resources/cases/prover_1.0.*/**/*.json")] path: PathBuf,
+    ) {
+        let case: Case = read_all(path).unwrap();
+
+        let proof = case.get_proof().unwrap();
+
+        assert_eq!(proof.detect_version(), Some(VerifierVersion::V1_0));
+        assert_eq!(
+            proof.verify_auto(case.vk, case.journal.digest()),
+            Ok(VerifierVersion::V1_0)
+        );
+    }
 }
 
 mod v1_1 {
@@ -130,6 +372,21 @@ mod v1_1 {
 
         proof.verify(&ctx, case.vk, case.journal.digest()).unwrap()
     }
+
+    #[rstest]
+    fn verify_auto_detects_the_version(
+        #[files("./resources/cases/prover_1.1.*/**/*.json")] path: PathBuf,
+    ) {
+        let case: Case = read_all(path).unwrap();
+
+        let proof = case.get_proof().unwrap();
+
+        assert_eq!(proof.detect_version(), Some(VerifierVersion::V1_1));
+        assert_eq!(
+            proof.verify_auto(case.vk, case.journal.digest()),
+            Ok(VerifierVersion::V1_1)
+        );
+    }
 }
 
 mod v1_2 {
@@ -149,6 +406,21 @@ mod v1_2 {
 
         proof.verify(&ctx, case.vk, case.journal.digest()).unwrap()
     }
+
+    #[rstest]
+    fn verify_auto_detects_the_version(
+        #[files("./resources/cases/prover_1.2.*/**/*.json")] path: PathBuf,
+    ) {
+        let case: Case = read_all(path).unwrap();
+
+        let proof = case.get_proof().unwrap();
+
+        assert_eq!(proof.detect_version(), Some(VerifierVersion::V1_2));
+        assert_eq!(
+            proof.verify_auto(case.vk, case.journal.digest()),
+            Ok(VerifierVersion::V1_2)
+        );
+    }
 }
 
 mod use_custom_local_implemented_hash_function {
@@ -165,7 +437,7 @@ mod use_custom_local_implemented_hash_function {
 
     impl HashFn<BabyBear> for CorrectSha256 {
         fn hash_pair(&self, a: &Digest, b: &Digest) -> Box<Digest> {
-            Impl::hash_pair(a, b).into()
+            Impl::hash_pair(a, b)
         }
 
         fn hash_elem_slice(&self, slice: &[<BabyBear as Field>::Elem]) -> Box<Digest> {
@@ -184,110 +456,1713 @@ mod use_custom_local_implemented_hash_function {
             (Digest::ZERO).into()
         }
 
-        fn hash_elem_slice(&self, _slice: &[<BabyBear as Field>::Elem]) -> Box<Digest> {
-            (Digest::ZERO).into()
-        }
+        fn hash_elem_slice(&self, _slice: &[<BabyBear as Field>::Elem]) -> Box<Digest> {
+            (Digest::ZERO).into()
+        }
+
+        fn hash_ext_elem_slice(&self, _slice: &[<BabyBear as Field>::ExtElem]) -> Box<Digest> {
+            (Digest::ZERO).into()
+        }
+    }
+
+    #[test]
+    fn should_work() {
+        let mut ctx = VerifierContext::v1_2();
+        let mut suites = ctx.suites.clone();
+        let mut sha = suites.get("sha-256").cloned().unwrap();
+        sha.hashfn = std::rc::Rc::new(CorrectSha256);
+
+        suites.insert("sha-256".to_owned(), sha).unwrap();
+        ctx = ctx.with_suites(suites);
+
+        let case: Case = read_all("./resources/cases/prover_1.2.0/vm_1.2.0/sha_16.json").unwrap();
+
+        let proof = case.get_proof().unwrap();
+
+        proof.verify(&ctx, case.vk, case.journal.digest()).unwrap()
+    }
+
+    #[test]
+    fn should_fail() {
+        let mut ctx = VerifierContext::v1_2();
+        let mut suites = ctx.suites.clone();
+        let mut sha = suites.get("sha-256").cloned().unwrap();
+        sha.hashfn = std::rc::Rc::new(FakeSha256);
+
+        suites.insert("sha-256".to_owned(), sha).unwrap();
+        ctx = ctx.with_suites(suites);
+
+        let case: Case = read_all("./resources/cases/prover_1.2.0/vm_1.2.0/sha_16.json").unwrap();
+
+        let proof = case.get_proof().unwrap();
+
+        proof
+            .verify(&ctx, case.vk, case.journal.digest())
+            .unwrap_err();
+    }
+
+    pub struct LocPoseidon2;
+
+    impl Poseidon2Mix for LocPoseidon2 {
+        #[inline]
+        fn poseidon2_mix(cells: &mut [BabyBearElem; POSEIDON2_CELLS]) {
+            poseidon2_mix(cells);
+        }
+    }
+
+    #[test]
+    fn should_poseidon2_work() {
+        let ctx = VerifierContext::v1_2().with_poseidon2_mix(LocPoseidon2);
+
+        let case: Case =
+            read_all("./resources/cases/prover_1.2.0/vm_1.2.0/poseidon2_22.json").unwrap();
+
+        let proof = case.get_proof().unwrap();
+
+        proof.verify(&ctx, case.vk, case.journal.digest()).unwrap()
+    }
+}
+
+#[rstest_reuse::apply(segments)]
+fn fails_on_invalid_segment<SC: CircuitCoreDef, RC: CircuitCoreDef>(
+    #[case] ctx: VerifierContext<SC, RC>,
+    #[case] path: &str,
+    #[values(0, 1, 2)] segment: usize,
+) {
+    let case: Case = read_all(path).unwrap();
+    let mut proof = case.get_proof().unwrap();
+
+    let seal = proof.inner.mut_composite().unwrap().segments[segment]
+        .seal
+        .as_mut_slice();
+
+    seal[seal.len() / 2] = seal[seal.len() / 2].wrapping_add(1);
+
+    let res = proof.verify(&ctx, case.vk, case.journal.digest());
+
+    assert!(res.is_err());
+    assert!(matches!(res, Err(VerificationError::InvalidProof)));
+}
+
+#[rstest]
+#[case::poseidon_proof_v1_1(
+    VerifierContext::v1_1,
+    "./resources/cases/prover_1.1.3/vm_1.1.3/poseidon2_22.json"
+)]
+#[case::sha_proof_v1_2(
+    VerifierContext::v1_2,
+    "./resources/cases/prover_1.2.0/vm_1.2.0/sha_22.json"
+)]
+fn verify_composite_in_parallel<SC: CircuitCoreDef + Sync, RC: CircuitCoreDef + Sync>(
+    #[case] ctx: fn() -> VerifierContext<SC, RC>,
+    #[case] path: &str,
+) {
+    let case: Case = read_all(path).unwrap();
+    let mut proof = case.get_proof().unwrap();
+    let composite = proof.inner.mut_composite().unwrap();
+
+    composite
+        .verify_integrity_with_context_parallel(ctx)
+        .unwrap();
+
+    for segment in [2, 1] {
+        let seal = composite.segments[segment].seal.as_mut_slice();
+        seal[seal.len() / 2] = seal[seal.len() / 2].wrapping_add(1);
+    }
+
+    assert_eq!(
+        composite.verify_integrity_with_context_parallel(ctx),
+        Err(VerificationError::InvalidProof)
+    );
+}
+
+#[rstest_reuse::apply(segments)]
+fn exposes_the_segments<SC: CircuitCoreDef, RC: CircuitCoreDef>(
+    #[case] _ctx: VerifierContext<SC, RC>,
+    #[case] path: &str,
+) {
+    let case: Case = read_all(path).unwrap();
+    let proof = case.get_proof().unwrap();
+    let composite = proof.inner.composite().unwrap();
+
+    assert!(composite.segment_count() > 1);
+    assert_eq!(composite.segments().count(), composite.segment_count());
+    assert!(composite
+        .segments()
+        .enumerate()
+        .all(|(i, segment)| segment.index as usize == i));
+}
+
+#[rstest_reuse::apply(all)]
+fn exposes_the_receipt_kind<SC: CircuitCoreDef, RC: CircuitCoreDef>(
+    #[case] _ctx: VerifierContext<SC, RC>,
+    #[case] path: &str,
+) {
+    let case: Case = read_all(path).unwrap();
+    let proof = case.get_proof().unwrap();
+
+    let expected = if path.contains("succinct") {
+        ReceiptKind::Succinct
+    } else {
+        ReceiptKind::Composite
+    };
+    assert_eq!(proof.kind(), expected);
+    assert_eq!(proof.is_succinct(), expected == ReceiptKind::Succinct);
+    assert_eq!(proof.is_composite(), expected == ReceiptKind::Composite);
+}
+
+#[rstest_reuse::apply(all)]
+fn proofs_can_be_deduplicated<SC: CircuitCoreDef, RC: CircuitCoreDef>(
+    #[case] _ctx: VerifierContext<SC, RC>,
+    #[case] path: &str,
+) {
+    let case: Case = read_all(path).unwrap();
+    let proof = case.get_proof().unwrap();
+    let mut tampered = proof.clone();
+    match &mut tampered.inner {
+        InnerReceipt::Composite(c) => c.segments[0].seal[0] ^= 1,
+        InnerReceipt::Succinct(s) => s.seal[0] ^= 1,
+        #[allow(unreachable_patterns)]
+        _ => unreachable!(),
+    }
+
+    let proofs: std::collections::HashSet<_> = [proof.clone(), proof.clone(), tampered.clone()]
+        .into_iter()
+        .collect();
+
+    assert_eq!(proofs.len(), 2);
+    assert!(proofs.contains(&proof));
+    assert!(proofs.contains(&tampered));
+    assert_ne!(proof, tampered);
+}
+
+#[rstest_reuse::apply(succinct)]
+fn exposes_the_control_inclusion_proof<SC: CircuitCoreDef, RC: CircuitCoreDef>(
+    #[case] ctx: VerifierContext<SC, RC>,
+    #[case] path: &str,
+) {
+    let case: Case = read_all(path).unwrap();
+    let proof = case.get_proof().unwrap();
+    let succinct = proof.inner.succinct().unwrap();
+    let hashfn = ctx.suites[&succinct.hashfn].hashfn.as_ref();
+    let control_root = ctx
+        .succinct_verifier_parameters
+        .as_ref()
+        .unwrap()
+        .control_root;
+
+    let (leaf, merkle_proof) = succinct.control_inclusion();
+
+    assert_eq!(leaf, succinct.control_id);
+    assert_eq!(merkle_proof.root(&leaf, hashfn), control_root);
+    assert_eq!(
+        risc0_verifier::merkle::verify_proof(leaf, &merkle_proof, control_root, hashfn),
+        Ok(())
+    );
+    assert!(merkle_proof
+        .verify(&Digest::ZERO, &control_root, hashfn)
+        .is_err());
+}
+
+#[rstest_reuse::apply(all)]
+fn explains_the_claim_mismatch<SC: CircuitCoreDef, RC: CircuitCoreDef>(
+    #[case] _ctx: VerifierContext<SC, RC>,
+    #[case] path: &str,
+) {
+    let case: Case = read_all(path).unwrap();
+    let proof = case.get_proof().unwrap();
+    let wrong_journal = Journal::new(vec![1, 2, 3, 4]).digest();
+
+    assert!(proof
+        .explain_mismatch(case.vk.0, case.journal.digest())
+        .unwrap()
+        .is_empty());
+    let diff = proof.explain_mismatch(case.vk.0, wrong_journal).unwrap();
+    assert_eq!(
+        diff.journal,
+        Some(risc0_verifier::Mismatch {
+            expected: wrong_journal,
+            found: case.journal.digest()
+        })
+    );
+    assert_eq!(diff.to_string(), "journal");
+    assert_eq!(
+        proof
+            .explain_mismatch(Digest::ZERO, wrong_journal)
+            .unwrap()
+            .to_string(),
+        "image id, journal"
+    );
+}
+
+#[rstest_reuse::apply(succinct)]
+fn succinct_verification_uses_the_context_control_root<SC: CircuitCoreDef, RC: CircuitCoreDef>(
+    #[case] ctx: VerifierContext<SC, RC>,
+    #[case] path: &str,
+) {
+    let case: Case = read_all(path).unwrap();
+    let proof = case.get_proof().unwrap();
+    let root = ctx.control_root().unwrap();
+    let ctx = ctx.with_control_root(Digest::from([1, 2, 3, 4, 5, 6, 7, 8]));
+
+    assert_eq!(ctx.name(), "risc0-custom");
+    assert!(matches!(
+        proof.verify(&ctx, case.vk.0, case.journal.digest()),
+        Err(VerificationError::ControlVerificationError { .. })
+    ));
+    proof
+        .verify(
+            &ctx.with_control_root(root),
+            case.vk.0,
+            case.journal.digest(),
+        )
+        .unwrap();
+}
+
+#[rstest_reuse::apply(succinct)]
+fn succinct_receipt_recovers_the_claim_type<SC: CircuitCoreDef, RC: CircuitCoreDef>(
+    #[case] ctx: VerifierContext<SC, RC>,
+    #[case] path: &str,
+) {
+    let case: Case = read_all(path).unwrap();
+    let succinct = case.get_proof().unwrap().inner.succinct().unwrap().clone();
+    let claim_digest = succinct.claim.digest();
+
+    let recovered = succinct.into_unknown().try_into_claim().unwrap();
+
+    assert_eq!(recovered.claim, MaybePruned::Pruned(claim_digest));
+    recovered.verify_integrity_with_context(&ctx).unwrap();
+}
+
+#[rstest_reuse::apply(all)]
+fn verify_to_the_attested_facts<SC: CircuitCoreDef, RC: CircuitCoreDef>(
+    #[case] ctx: VerifierContext<SC, RC>,
+    #[case] path: &str,
+) {
+    let case: Case = read_all(path).unwrap();
+    let proof = case.get_proof().unwrap();
+
+    let facts = proof
+        .verify_to_facts(&ctx, case.vk.0, case.journal.digest())
+        .unwrap();
+
+    assert_eq!(facts.image_id, case.vk.0);
+    assert_eq!(facts.journal_digest, case.journal.digest());
+    assert_eq!(facts.exit_code, risc0_verifier::ExitCode::Halted(0));
+    assert_eq!(facts.verifier_parameters, proof.verifier_parameters());
+    let json = serde_json::to_string(&facts).unwrap();
+    assert_eq!(
+        serde_json::from_str::<risc0_verifier::VerifiedFacts>(&json).unwrap(),
+        facts
+    );
+    assert!(proof.verify_to_facts(&ctx, case.vk, Digest::ZERO).is_err());
+}
+
+#[rstest_reuse::apply(all)]
+fn build_the_receipt_from_either_arm<SC: CircuitCoreDef, RC: CircuitCoreDef>(
+    #[case] _ctx: VerifierContext<SC, RC>,
+    #[case] path: &str,
+) {
+    let case: Case = read_all(path).unwrap();
+    let inner = case.get_proof().unwrap().inner;
+
+    #[allow(unreachable_patterns)]
+    let rebuilt: InnerReceipt = match &inner {
+        InnerReceipt::Composite(composite) => composite.clone().into(),
+        InnerReceipt::Succinct(succinct) => succinct.clone().into(),
+        _ => unreachable!(),
+    };
+
+    assert_eq!(rebuilt, inner);
+}
+
+#[rstest_reuse::apply(all)]
+fn verify_the_assumption_as_the_expected_claim<SC: CircuitCoreDef, RC: CircuitCoreDef>(
+    #[case] ctx: VerifierContext<SC, RC>,
+    #[case] path: &str,
+) {
+    let case: Case = read_all(path).unwrap();
+    let proof = case.get_proof().unwrap();
+    let claim = proof.claim().unwrap();
+    let assumption = risc0_verifier::InnerAssumptionReceipt::from(proof.inner);
+
+    let other = ReceiptClaim::ok(Digest::ZERO, MaybePruned::Pruned(Digest::ZERO));
+
+    assumption.verify_as(&ctx, &claim).unwrap();
+    assert_eq!(
+        assumption.verify_as(&ctx, &other),
+        Err(VerificationError::ClaimDigestMismatch {
+            expected: other.digest(),
+            received: claim.digest(),
+        })
+    );
+}
+
+#[rstest_reuse::apply(all)]
+fn assumption_receipt_converts_back_into_a_receipt<SC: CircuitCoreDef, RC: CircuitCoreDef>(
+    #[case] ctx: VerifierContext<SC, RC>,
+    #[case] path: &str,
+) {
+    let case: Case = read_all(path).unwrap();
+    let proof = case.get_proof().unwrap();
+    let claim_digest = proof.claim().unwrap().digest();
+
+    let inner = risc0_verifier::InnerAssumptionReceipt::from(proof.inner.clone())
+        .try_into_receipt()
+        .unwrap();
+
+    if proof.is_composite() {
+        assert_eq!(inner, proof.inner);
+    } else {
+        assert_eq!(inner.claim().unwrap(), MaybePruned::Pruned(claim_digest));
+    }
+    Proof::new(inner)
+        .verify_claim_digest(&ctx, claim_digest)
+        .unwrap();
+}
+
+#[rstest_reuse::apply(all)]
+fn claim_digest_for_matches_the_proof_claim<SC: CircuitCoreDef, RC: CircuitCoreDef>(
+    #[case] _ctx: VerifierContext<SC, RC>,
+    #[case] path: &str,
+) {
+    use risc0_verifier::{claim_digest_for, ExitCode};
+
+    let case: Case = read_all(path).unwrap();
+    let proof = case.get_proof().unwrap();
+
+    assert_eq!(
+        claim_digest_for(case.vk.0, case.journal.digest(), ExitCode::Halted(0)),
+        proof.claim().unwrap().digest()
+    );
+}
+
+#[rstest_reuse::apply(all)]
+fn verify_claim_digest_checks_the_built_claim<SC: CircuitCoreDef, RC: CircuitCoreDef>(
+    #[case] ctx: VerifierContext<SC, RC>,
+    #[case] path: &str,
+) {
+    use risc0_verifier::ExitCode;
+
+    let case: Case = read_all(path).unwrap();
+    let proof = case.get_proof().unwrap();
+    let builder = ReceiptClaim::builder()
+        .image_id(case.vk.0)
+        .journal_digest(case.journal.digest());
+
+    proof
+        .verify_claim_digest(&ctx, builder.clone().build().digest())
+        .unwrap();
+    assert!(matches!(
+        proof.verify_claim_digest(
+            &ctx,
+            builder.exit_code(ExitCode::Paused(0)).build().digest()
+        ),
+        Err(VerificationError::ClaimDigestMismatch { .. })
+    ));
+}
+
+#[rstest_reuse::apply(all)]
+fn verify_with_the_journal_bytes<SC: CircuitCoreDef, RC: CircuitCoreDef>(
+    #[case] ctx: VerifierContext<SC, RC>,
+    #[case] path: &str,
+) {
+    let case: Case = read_all(path).unwrap();
+    let proof = case.get_proof().unwrap();
+
+    proof
+        .verify_with_journal(&ctx, case.vk.0, &case.journal)
+        .unwrap();
+    assert!(matches!(
+        proof.verify_with_journal(&ctx, case.vk.0, &Journal::new(vec![1, 2, 3, 4])),
+        Err(VerificationError::ClaimDigestMismatch { .. })
+    ));
+    proof
+        .verify_with_expected_journal(&ctx, case.vk.0, &case.journal.bytes)
+        .unwrap();
+    let mut truncated = case.journal.bytes.clone();
+    truncated.pop();
+    assert!(matches!(
+        proof.verify_with_expected_journal(&ctx, case.vk.0, &truncated),
+        Err(VerificationError::ClaimDigestMismatch { .. })
+    ));
+
+    let len = case.journal.bytes.len();
+    let ctx = ctx.with_max_journal_len(len);
+    proof
+        .verify_with_journal(&ctx, case.vk.0, &case.journal)
+        .unwrap();
+    assert_eq!(
+        proof.verify_with_journal(&ctx, case.vk.0, &Journal::new(vec![0; len + 1])),
+        Err(VerificationError::ReceiptFormatError)
+    );
+}
+
+#[rstest_reuse::apply(all)]
+fn journal_digest_is_sha256_whatever_the_seal_hash_fn<SC: CircuitCoreDef, RC: CircuitCoreDef>(
+    #[case] ctx: VerifierContext<SC, RC>,
+    #[case] path: &str,
+) {
+    let case: Case = read_all(path).unwrap();
+    let proof = case.get_proof().unwrap();
+    let claim = proof
+        .verify_and_extract(&ctx, case.vk.0, case.journal.digest())
+        .unwrap();
+    let output = claim.output.as_value().unwrap().as_ref().unwrap();
+
+    use sha2::Digest as _;
+    assert_eq!(
+        output.journal.digest().as_bytes(),
+        sha2::Sha256::digest(&case.journal.bytes).as_slice()
+    );
+}
+
+#[rstest_reuse::apply(succinct)]
+fn verify_unknown_claim_succinct_against_its_digest<SC: CircuitCoreDef, RC: CircuitCoreDef>(
+    #[case] ctx: VerifierContext<SC, RC>,
+    #[case] path: &str,
+) {
+    let case: Case = read_all(path).unwrap();
+    let proof = case.get_proof().unwrap();
+    let claim_digest = proof.claim().unwrap().digest();
+    let unknown = proof.inner.succinct().unwrap().clone().into_unknown();
+
+    unknown
+        .verify_integrity_against(&ctx, claim_digest)
+        .unwrap();
+    assert_eq!(
+        unknown.verify_integrity_against(&ctx, Digest::ZERO),
+        Err(VerificationError::ClaimDigestMismatch {
+            expected: Digest::ZERO,
+            received: claim_digest
+        })
+    );
+}
+
+#[rstest_reuse::apply(all)]
+fn verify_integrity_then_inspect_the_claim<SC: CircuitCoreDef, RC: CircuitCoreDef>(
+    #[case] ctx: VerifierContext<SC, RC>,
+    #[case] path: &str,
+) {
+    let case: Case = read_all(path).unwrap();
+    let proof = case.get_proof().unwrap();
+
+    proof.verify_integrity(&ctx).unwrap();
+    assert_eq!(
+        proof.claim().unwrap().digest(),
+        ReceiptClaim::ok(case.vk.0, MaybePruned::Pruned(case.journal.digest())).digest()
+    );
+}
+
+#[derive(Debug, PartialEq)]
+enum Event {
+    SegmentStart(usize, u32),
+    SegmentEnd(usize, bool),
+    SuccinctStart,
+    SuccinctEnd(bool),
+}
+
+#[derive(Default)]
+struct Recorder(std::rc::Rc<std::cell::RefCell<Vec<Event>>>);
+
+impl risc0_verifier::VerifyObserver for Recorder {
+    fn on_segment_start(&self, idx: usize, po2: u32) {
+        self.0.borrow_mut().push(Event::SegmentStart(idx, po2));
+    }
+
+    fn on_segment_end(&self, idx: usize, result: &Result<(), VerificationError>) {
+        self.0
+            .borrow_mut()
+            .push(Event::SegmentEnd(idx, result.is_ok()));
+    }
+
+    fn on_succinct_start(&self) {
+        self.0.borrow_mut().push(Event::SuccinctStart);
+    }
+
+    fn on_succinct_end(&self, result: &Result<(), VerificationError>) {
+        self.0.borrow_mut().push(Event::SuccinctEnd(result.is_ok()));
+    }
+}
+
+#[rstest_reuse::apply(all)]
+fn observer_is_notified_about_the_verification<SC: CircuitCoreDef, RC: CircuitCoreDef>(
+    #[case] ctx: VerifierContext<SC, RC>,
+    #[case] path: &str,
+) {
+    let case: Case = read_all(path).unwrap();
+    let proof = case.get_proof().unwrap();
+    let recorder = Recorder::default();
+    let events = recorder.0.clone();
+    let ctx = ctx.with_observer(recorder);
+
+    proof
+        .verify(&ctx, case.vk.0, case.journal.digest())
+        .unwrap();
+
+    let events = events.take();
+    match proof.inner.composite() {
+        Ok(composite) => {
+            assert_eq!(events.len(), 2 * composite.segment_count());
+            for (idx, pair) in events.chunks(2).enumerate() {
+                let Event::SegmentStart(start_idx, po2) = pair[0] else {
+                    panic!("unexpected event {:?}", pair[0]);
+                };
+                assert_eq!(start_idx, idx);
+                assert!((13..=risc0_verifier::DEFAULT_MAX_PO2 as u32).contains(&po2));
+                assert_eq!(pair[1], Event::SegmentEnd(idx, true));
+            }
+        }
+        Err(_) => assert_eq!(events, [Event::SuccinctStart, Event::SuccinctEnd(true)]),
+    }
+}
+
+#[rstest_reuse::apply(all)]
+fn trace_records_the_checks_of_the_verification<SC: CircuitCoreDef, RC: CircuitCoreDef>(
+    #[case] ctx: VerifierContext<SC, RC>,
+    #[case] path: &str,
+) {
+    use risc0_verifier::{VerifyCheck, VerifyStep};
+    let step = |check, passed| VerifyStep { check, passed };
+    let case: Case = read_all(path).unwrap();
+    let proof = case.get_proof().unwrap();
+    let recorder = Recorder::default();
+    let events = recorder.0.clone();
+    let ctx = ctx.with_observer(recorder);
+
+    let (result, steps) = proof.verify_with_trace(&ctx, case.vk.0, case.journal.digest());
+
+    assert_eq!(result, proof.verify(&ctx, case.vk.0, case.journal.digest()));
+    result.unwrap();
+    assert!(!events.take().is_empty());
+    assert!(steps.iter().all(|step| step.passed));
+    assert!(steps.contains(&step(VerifyCheck::ControlId, true)));
+    assert_eq!(steps.last(), Some(&step(VerifyCheck::ClaimDigest, true)));
+    match proof.inner.composite() {
+        Ok(composite) => {
+            for idx in 0..composite.segment_count() {
+                assert!(steps.contains(&step(VerifyCheck::SegmentSeal(idx), true)));
+            }
+            assert!(steps.contains(&step(VerifyCheck::Continuation, true)));
+            assert!(steps.contains(&step(VerifyCheck::Assumptions, true)));
+        }
+        Err(_) => assert!(steps.contains(&step(VerifyCheck::SuccinctSeal, true))),
+    }
+
+    let pubs = Journal::new(vec![]).digest();
+    let (result, steps) = proof.verify_with_trace(&ctx, case.vk.0, pubs);
+
+    assert_eq!(result, proof.verify(&ctx, case.vk.0, pubs));
+    assert_eq!(steps.last(), Some(&step(VerifyCheck::ClaimDigest, false)));
+}
+
+#[rstest_reuse::apply(all)]
+fn borrow_the_stored_claim<SC: CircuitCoreDef, RC: CircuitCoreDef>(
+    #[case] _ctx: VerifierContext<SC, RC>,
+    #[case] path: &str,
+) {
+    let case: Case = read_all(path).unwrap();
+    let proof = case.get_proof().unwrap();
+
+    match proof.inner.composite() {
+        Ok(_) => assert_eq!(proof.inner.claim_ref(), None),
+        Err(_) => assert_eq!(proof.inner.claim_ref(), Some(&proof.claim().unwrap())),
+    }
+}
+
+#[rstest_reuse::apply(segments)]
+fn recognize_the_split_continuation<SC: CircuitCoreDef, RC: CircuitCoreDef>(
+    #[case] ctx: VerifierContext<SC, RC>,
+    #[case] path: &str,
+) {
+    use risc0_verifier::{Digest, ExitCode};
+    let case: Case = read_all(path).unwrap();
+    let mut proof = case.get_proof().unwrap();
+    // The first segment alone proves an execution that hasn't terminated yet.
+    proof.inner.mut_composite().unwrap().segments.truncate(1);
+    let pubs = case.journal.digest();
+
+    proof.inner.verify_integrity_with_context(&ctx).unwrap();
+    assert_eq!(
+        proof.claim().unwrap().value().unwrap().exit_code,
+        ExitCode::SystemSplit
+    );
+    assert_eq!(
+        proof.verify(&ctx, case.vk.0, pubs),
+        Err(VerificationError::UnexpectedExitCode)
+    );
+    // The crate level errors report the exit code.
+    let unexpected = risc0_verifier::Error::UnexpectedExitCode {
+        expected: ExitCode::Halted(0),
+        found: ExitCode::SystemSplit,
+    };
+    assert_eq!(
+        proof.verify_and_extract(&ctx, case.vk.0, pubs).unwrap_err(),
+        unexpected
+    );
+    assert_eq!(
+        proof
+            .verify_any(&ctx, &[Digest::ZERO, case.vk.0], pubs)
+            .unwrap_err(),
+        unexpected
+    );
+    assert_eq!(proof.verify_auto(case.vk.0, pubs).unwrap_err(), unexpected);
+    assert_eq!(
+        proof.verify_trying_all(case.vk.0, pubs).unwrap_err(),
+        unexpected
+    );
+    assert_eq!(
+        proof.verify_with_exit(&ctx, case.vk.0, pubs, &[ExitCode::SystemSplit]),
+        Ok(ExitCode::SystemSplit)
+    );
+    assert_eq!(
+        proof.verify_with_exit(&ctx, case.vk.0, pubs, &[ExitCode::Halted(0)]),
+        Err(VerificationError::UnexpectedExitCode)
+    );
+    assert!(matches!(
+        proof.verify_with_exit(&ctx, Digest::ZERO, pubs, &[ExitCode::SystemSplit]),
+        Err(VerificationError::ClaimDigestMismatch { .. })
+    ));
+}
+
+#[rstest_reuse::apply(segments)]
+fn exposes_the_segment_po2<SC: CircuitCoreDef, RC: CircuitCoreDef>(
+    #[case] ctx: VerifierContext<SC, RC>,
+    #[case] path: &str,
+) {
+    let case: Case = read_all(path).unwrap();
+    let mut proof = case.get_proof().unwrap();
+
+    for segment in proof.inner.composite().unwrap().segments() {
+        let po2 = segment.po2::<SC>().unwrap();
+        assert!((13..=ctx.max_po2).contains(&po2));
+        assert_eq!(segment.cycles::<SC>().unwrap(), 1 << po2);
+    }
+
+    let segment = &mut proof.inner.mut_composite().unwrap().segments[0];
+    segment.seal[SC::OUTPUT_SIZE] = 25;
+    assert_eq!(
+        segment.po2::<SC>(),
+        Err(VerificationError::ReceiptFormatError)
+    );
+    assert_eq!(
+        proof.verify(&ctx, case.vk, case.journal.digest()),
+        Err(VerificationError::ReceiptFormatError)
+    );
+}
+
+#[rstest_reuse::apply(segments)]
+fn iterates_the_segment_claims<SC: CircuitCoreDef, RC: CircuitCoreDef>(
+    #[case] _ctx: VerifierContext<SC, RC>,
+    #[case] path: &str,
+) {
+    let case: Case = read_all(path).unwrap();
+    let mut proof = case.get_proof().unwrap();
+    let claims = proof
+        .inner
+        .composite()
+        .unwrap()
+        .iter_segment_claims()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+
+    assert_eq!(claims[0].pre_state_digest(), case.vk.0);
+    assert!(claims
+        .windows(2)
+        .all(|pair| pair[0].post_state_digest() == pair[1].pre_state_digest()));
+
+    let segment = &mut proof.inner.mut_composite().unwrap().segments[1];
+    segment.claim.post = MaybePruned::Pruned(segment.claim.post_state_digest());
+    let results = proof
+        .inner
+        .composite()
+        .unwrap()
+        .iter_segment_claims()
+        .map(|claim| claim.map(|_| ()))
+        .collect::<Vec<_>>();
+    assert_eq!(results[0], Ok(()));
+    assert_eq!(results[1], Err(VerificationError::ReceiptFormatError));
+}
+
+#[test]
+fn verify_assumptions_matches_the_resolvers_by_claim_digest() {
+    use risc0_verifier::{Assumption, Assumptions, InnerAssumptionReceipt, Output};
+
+    let ctx = VerifierContext::v1_2();
+    let resolver: InnerAssumptionReceipt =
+        read_all::<Case>("./resources/cases/prover_1.2.0/vm_1.2.0/succinct_22.json")
+            .unwrap()
+            .get_proof()
+            .unwrap()
+            .inner
+            .into();
+    let other: InnerAssumptionReceipt =
+        read_all::<Case>("./resources/cases/prover_1.2.0/vm_1.2.0/succinct_16.json")
+            .unwrap()
+            .get_proof()
+            .unwrap()
+            .inner
+            .into();
+    let digest = resolver.claim_digest().unwrap();
+
+    let mut proof = read_all::<Case>("./resources/cases/prover_1.2.0/vm_1.2.0/sha_22.json")
+        .unwrap()
+        .get_proof()
+        .unwrap();
+    let composite = proof.inner.mut_composite().unwrap();
+    composite.verify_assumptions(&ctx, &[]).unwrap();
+
+    composite.segments.last_mut().unwrap().claim.output = MaybePruned::Value(Some(Output {
+        journal: MaybePruned::Value(vec![]),
+        assumptions: MaybePruned::Value(Assumptions(vec![MaybePruned::Value(Assumption {
+            claim: digest,
+            control_root: risc0_verifier::Digest::ZERO,
+        })])),
+    }));
+
+    composite
+        .verify_assumptions(&ctx, &[other.clone(), resolver])
+        .unwrap();
+    assert_eq!(
+        composite.verify_assumptions(&ctx, &[other]),
+        Err(VerificationError::UnresolvedAssumption { digest })
+    );
+}
+
+#[test]
+fn assumptions_with_a_control_root_keep_the_context_policy() {
+    use risc0_verifier::{Assumption, Assumptions, InnerAssumptionReceipt, Output};
+
+    let ctx = VerifierContext::v1_2();
+    let succinct = read_all::<Case>("./resources/cases/prover_1.2.0/vm_1.2.0/succinct_22.json")
+        .unwrap()
+        .get_proof()
+        .unwrap();
+    let control_id = succinct.inner.succinct().unwrap().control_id;
+    let resolver = InnerAssumptionReceipt::from(succinct.inner);
+    let mut proof = read_all::<Case>("./resources/cases/prover_1.2.0/vm_1.2.0/sha_22.json")
+        .unwrap()
+        .get_proof()
+        .unwrap();
+    let composite = proof.inner.mut_composite().unwrap();
+    // The guest provides the control root of the assumption.
+    composite.segments.last_mut().unwrap().claim.output = MaybePruned::Value(Some(Output {
+        journal: MaybePruned::Value(vec![]),
+        assumptions: MaybePruned::Value(Assumptions(vec![MaybePruned::Value(Assumption {
+            claim: resolver.claim_digest().unwrap(),
+            control_root: ctx.control_root().unwrap(),
+        })])),
+    }));
+
+    composite
+        .verify_assumptions(&ctx, core::slice::from_ref(&resolver))
+        .unwrap();
+    let ctx = ctx.with_allowed_control_ids([risc0_verifier::Digest::ZERO]);
+    assert_eq!(
+        composite.verify_assumptions(&ctx, &[resolver]),
+        Err(VerificationError::ControlVerificationError { control_id })
+    );
+}
+
+#[test]
+fn require_no_assumptions_rejects_the_composed_proofs() {
+    use risc0_verifier::{Assumption, Assumptions, InnerAssumptionReceipt, Output};
+
+    let read = |path| read_all::<Case>(path).unwrap().get_proof().unwrap();
+    let mut composite = read("./resources/cases/prover_1.2.0/vm_1.2.0/sha_16.json");
+    let mut succinct = read("./resources/cases/prover_1.2.0/vm_1.2.0/succinct_16.json");
+    composite.require_no_assumptions().unwrap();
+    succinct.require_no_assumptions().unwrap();
+
+    let resolver = InnerAssumptionReceipt::from(succinct.inner.clone());
+    let digest = resolver.claim_digest().unwrap();
+    composite
+        .inner
+        .mut_composite()
+        .unwrap()
+        .assumption_receipts
+        .push(resolver);
+    assert_eq!(
+        composite.require_no_assumptions(),
+        Err(VerificationError::UnresolvedAssumption { digest })
+    );
+
+    let assumptions = Assumptions(vec![MaybePruned::Value(Assumption {
+        claim: digest,
+        control_root: risc0_verifier::Digest::ZERO,
+    })]);
+    let output = MaybePruned::Value(Some(Output {
+        journal: MaybePruned::Value(vec![]),
+        assumptions: MaybePruned::Value(assumptions.clone()),
+    }));
+    let claim = &mut succinct.inner.mut_succinct().unwrap().claim;
+    claim.as_value_mut().unwrap().output = output.clone();
+    assert_eq!(
+        succinct.require_no_assumptions(),
+        Err(VerificationError::UnresolvedAssumption {
+            digest: assumptions.digest()
+        })
+    );
+    let claim = &mut succinct.inner.mut_succinct().unwrap().claim;
+    claim.as_value_mut().unwrap().output = MaybePruned::Pruned(output.digest());
+    assert_eq!(
+        succinct.require_no_assumptions(),
+        Err(VerificationError::ReceiptFormatError)
+    );
+}
+
+#[rstest_reuse::apply(succinct)]
+fn fails_on_invalid_succinct<SC: CircuitCoreDef, RC: CircuitCoreDef>(
+    #[case] ctx: VerifierContext<SC, RC>,
+    #[case] path: &str,
+) {
+    let case: Case = read_all(path).unwrap();
+    let mut proof = case.get_proof().unwrap();
+
+    let seal = proof.inner.mut_succinct().unwrap().seal.as_mut_slice();
+
+    seal[seal.len() / 2] = seal[seal.len() / 2].wrapping_add(1);
+
+    let res = proof.verify(&ctx, case.vk, case.journal.digest());
+
+    assert!(res.is_err());
+    assert!(matches!(res, Err(VerificationError::InvalidProof)));
+}
+
+#[rstest_reuse::apply(succinct)]
+fn strict_context_rejects_a_pruned_claim<SC: CircuitCoreDef, RC: CircuitCoreDef>(
+    #[case] ctx: VerifierContext<SC, RC>,
+    #[case] path: &str,
+) {
+    let case: Case = read_all(path).unwrap();
+    let proof = case.get_proof().unwrap();
+    let mut pruned = proof.clone();
+    let claim = &mut pruned.inner.mut_succinct().unwrap().claim;
+    *claim = MaybePruned::Pruned(claim.digest());
+
+    pruned
+        .verify(&ctx, case.vk.0, case.journal.digest())
+        .unwrap();
+    let ctx = ctx.with_strict_claims(true);
+    proof
+        .verify(&ctx, case.vk.0, case.journal.digest())
+        .unwrap();
+    assert_eq!(
+        pruned.verify(&ctx, case.vk.0, case.journal.digest()),
+        Err(VerificationError::ReceiptFormatError)
+    );
+}
+
+#[rstest]
+#[case::composite("./resources/cases/prover_1.2.0/vm_1.2.0/poseidon2_16.json")]
+#[case::succinct("./resources/cases/prover_1.2.0/vm_1.2.0/succinct_16.json")]
+fn custom_context_verifies_with_the_given_circuits(#[case] path: &str) {
+    use risc0_verifier::circuit::{v1_2, HashFn};
+    let control_ids = HashFn::ALL.into_iter().flat_map(|hash| {
+        v1_2::control_ids(hash)
+            .filter(|&(po2, _)| po2 <= risc0_verifier::DEFAULT_MAX_PO2)
+            .map(|(_, id)| id)
+    });
+    let ctx = VerifierContext::new(
+        &v1_2::CIRCUIT,
+        &v1_2::recursive::CIRCUIT,
+        control_ids,
+        VerifierContext::v1_2().control_root().unwrap(),
+    );
+    let case: Case = read_all(path).unwrap();
+    let proof = case.get_proof().unwrap();
+
+    assert_eq!(ctx.version(), Some(VerifierVersion::V1_2));
+    proof.verify(&ctx, case.vk, case.journal.digest()).unwrap();
+}
+
+#[test]
+fn builder_composes_the_context_options() {
+    use risc0_verifier::{circuit::HashFn, VerifierContextBuilder};
+    let ctx = VerifierContextBuilder::v1_2()
+        .max_po2(24)
+        .strict_claims(true)
+        .max_journal_len(1024)
+        .hash_fns([HashFn::Sha256])
+        .build()
+        .unwrap();
+    let chained = VerifierContext::v1_2()
+        .with_max_po2(24)
+        .unwrap()
+        .with_strict_claims(true)
+        .with_max_journal_len(1024);
+
+    assert_eq!(ctx.max_po2, chained.max_po2);
+    assert_eq!(
+        ctx.segment_verifier_parameters,
+        chained.segment_verifier_parameters
+    );
+    assert!(ctx.strict_claims);
+    assert_eq!(ctx.max_journal_len, Some(1024));
+    assert_eq!(ctx.suites.keys().collect::<Vec<_>>(), ["sha-256"]);
+
+    let case: Case = read_all("./resources/cases/prover_1.2.0/vm_1.2.0/sha_16.json").unwrap();
+    case.get_proof()
+        .unwrap()
+        .verify(&ctx, case.vk, case.journal.digest())
+        .unwrap();
+    let case: Case = read_all("./resources/cases/prover_1.2.0/vm_1.2.0/poseidon2_16.json").unwrap();
+    assert_eq!(
+        case.get_proof()
+            .unwrap()
+            .verify(&ctx, case.vk, case.journal.digest()),
+        Err(VerificationError::InvalidHashSuite)
+    );
+}
+
+#[test]
+fn builder_checks_the_max_po2() {
+    use risc0_verifier::VerifierContextBuilder;
+
+    assert!(matches!(
+        VerifierContextBuilder::v1_2().max_po2(30).build(),
+        Err(risc0_verifier::Error::Po2OutOfRange { po2: 30, .. })
+    ));
+    assert_eq!(
+        VerifierContextBuilder::from(VerifierContext::v1_0())
+            .max_po2(22)
+            .build()
+            .unwrap()
+            .max_po2,
+        22
+    );
+}
+
+#[cfg(feature = "test-utils")]
+#[test]
+fn succinct_receipt_from_parts() {
+    let case: Case = read_all("./resources/cases/prover_1.2.0/vm_1.2.0/succinct_16.json").unwrap();
+    let proof = case.get_proof().unwrap();
+    let receipt = proof.inner.succinct().unwrap();
+    let rebuilt = |claim| {
+        SuccinctReceipt::from_parts(
+            receipt.seal.clone(),
+            receipt.control_id,
+            claim,
+            receipt.hashfn.clone(),
+            receipt.verifier_parameters,
+            receipt.control_inclusion_proof.clone(),
+        )
+    };
+
+    assert_eq!(&rebuilt(receipt.claim.clone()), receipt);
+    let forged = Proof::new(
+        rebuilt(ReceiptClaim::ok(case.vk.0, MaybePruned::Pruned(Digest::ZERO)).into()).into(),
+    );
+    assert!(forged
+        .verify(&VerifierContext::v1_2(), case.vk, Digest::ZERO)
+        .is_err());
+}
+
+#[rstest_reuse::apply(all)]
+fn verify_trying_all_finds_the_version<SC: CircuitCoreDef, RC: CircuitCoreDef>(
+    #[case] ctx: VerifierContext<SC, RC>,
+    #[case] path: &str,
+) {
+    let case: Case = read_all(path).unwrap();
+    let mut proof = case.get_proof().unwrap();
+    let version = ctx.version().unwrap();
+
+    assert_eq!(
+        proof.verify_trying_all(case.vk.0, case.journal.digest()),
+        Ok(version)
+    );
+
+    // The declared verifier parameters don't matter.
+    let params = match &mut proof.inner {
+        InnerReceipt::Composite(inner) => &mut inner.verifier_parameters,
+        InnerReceipt::Succinct(inner) => &mut inner.verifier_parameters,
+        #[allow(unreachable_patterns)]
+        _ => unreachable!(),
+    };
+    *params = Digest::ZERO;
+    assert_eq!(proof.detect_version(), None);
+    assert_eq!(
+        proof.verify_trying_all(case.vk.0, case.journal.digest()),
+        Ok(version)
+    );
+    assert!(matches!(
+        proof.verify_trying_all(Digest::ZERO, case.journal.digest()),
+        Err(risc0_verifier::Error::Verification(_))
+    ));
+}
+
+#[rstest_reuse::apply(all)]
+fn only_the_own_context_accepts_the_seals<SC: CircuitCoreDef, RC: CircuitCoreDef>(
+    #[case] ctx: VerifierContext<SC, RC>,
+    #[case] path: &str,
+) {
+    let case: Case = read_all(path).unwrap();
+    let proof = case.get_proof().unwrap();
+    let pubs = case.journal.digest();
+    // The 1.1 SHA-256 segments make the 1.2 verifier panic: that's a rejection too.
+    let accepts = |verify: &dyn Fn() -> Result<(), VerificationError>| {
+        std::panic::catch_unwind(std::panic::AssertUnwindSafe(verify)).is_ok_and(|r| r.is_ok())
+    };
+    let accepted = [
+        accepts(&|| proof.verify(&VerifierContext::v1_0(), case.vk.0, pubs)),
+        accepts(&|| proof.verify(&VerifierContext::v1_1(), case.vk.0, pubs)),
+        accepts(&|| proof.verify(&VerifierContext::v1_2(), case.vk.0, pubs)),
+    ];
+
+    let version = ctx.version().unwrap();
+    assert_eq!(
+        accepted,
+        VerifierVersion::ALL.map(|candidate| candidate == version)
+    );
+    assert_eq!(proof.verify_trying_all(case.vk.0, pubs), Ok(version));
+}
+
+#[test]
+fn verify_auto_fails_on_unknown_verifier_parameters() {
+    let case: Case = read_all("./resources/cases/prover_1.2.0/vm_1.2.0/succinct_22.json").unwrap();
+    let mut proof = case.get_proof().unwrap();
+    let params = &mut proof.inner.mut_succinct().unwrap().verifier_parameters;
+    params.as_mut_words()[0] ^= 1;
+    let params = *params;
+
+    assert_eq!(proof.verifier_parameters(), params);
+    assert_eq!(proof.detect_version(), None);
+    assert_eq!(
+        proof.verify_auto(case.vk, case.journal.digest()),
+        Err(risc0_verifier::Error::UnknownVerifierParameters(params))
+    );
+}
+
+#[cfg(feature = "groth16")]
+mod groth16 {
+    use super::*;
+    use risc0_verifier::{Groth16Receipt, Groth16ReceiptVerifierParameters, MaybePruned};
+
+    /// The `TestReceipt` of `risc0-ethereum-contracts` 1.2.0, with the 4 bytes verifier selector
+    /// stripped from the seal.
+    #[derive(Deserialize)]
+    struct Fixture {
+        seal: String,
+        image_id: String,
+        journal: String,
+    }
+
+    fn fixture() -> (Proof, Digest, Journal) {
+        let Fixture {
+            seal,
+            image_id,
+            journal,
+        } = read_all("./resources/groth16/prover_1.2.0.json").unwrap();
+        let (seal, journal) = (hex::decode(seal).unwrap(), hex::decode(journal).unwrap());
+        let image_id = Digest::try_from(hex::decode(image_id).unwrap().as_slice()).unwrap();
+        let claim = ReceiptClaim::ok(image_id, journal.clone());
+        let receipt = Groth16Receipt::new(
+            seal,
+            MaybePruned::Value(claim),
+            Groth16ReceiptVerifierParameters::v1_2().digest(),
+        );
+        (
+            Proof::new(InnerReceipt::Groth16(receipt)),
+            image_id,
+            Journal::new(journal),
+        )
+    }
+
+    #[test]
+    fn verify_a_real_proof() {
+        let (proof, image_id, journal) = fixture();
+
+        assert_eq!(proof.kind(), ReceiptKind::Groth16);
+        proof
+            .verify(&VerifierContext::v1_2(), image_id, journal.digest())
+            .unwrap();
+    }
+
+    #[test]
+    fn reject_a_tampered_seal() {
+        let (mut proof, image_id, journal) = fixture();
+        let InnerReceipt::Groth16(receipt) = &mut proof.inner else {
+            unreachable!()
+        };
+        // Swap the `a` and `c` points: the seal is still well formed, but the pairing fails.
+        let (a, bc) = receipt.seal.split_at_mut(64);
+        a.swap_with_slice(&mut bc[128..]);
+
+        assert_eq!(
+            proof.verify(&VerifierContext::v1_2(), image_id, journal.digest()),
+            Err(VerificationError::InvalidProof)
+        );
+    }
+}
+
+#[cfg(feature = "unsafe-fake")]
+#[test]
+fn fake_receipt_checks_only_the_claim() {
+    let case: Case = read_all("./resources/cases/prover_1.2.0/vm_1.2.0/succinct_16.json").unwrap();
+    let claim = ReceiptClaim::ok(case.vk.0, case.journal.bytes.clone());
+    let proof = Proof::new(InnerReceipt::Fake(risc0_verifier::FakeReceipt::new(claim)));
+    let ctx = VerifierContext::v1_2();
+
+    assert_eq!(proof.kind(), ReceiptKind::Fake);
+    proof
+        .verify(&ctx, case.vk.0, case.journal.digest())
+        .unwrap();
+    assert!(matches!(
+        proof.verify(&ctx, case.vk.0, Journal::new(vec![]).digest()),
+        Err(VerificationError::ClaimDigestMismatch { .. })
+    ));
+}
+
+#[cfg(feature = "bincode")]
+#[rstest_reuse::apply(all)]
+fn bincode_round_trip<SC: CircuitCoreDef, RC: CircuitCoreDef>(
+    #[case] ctx: VerifierContext<SC, RC>,
+    #[case] path: &str,
+) {
+    let case: Case = read_all(path).unwrap();
+    let proof = case.get_proof().unwrap();
+
+    let bytes = proof.to_bincode().unwrap();
+    assert_eq!(bytes, bincode::serialize(&proof).unwrap());
+
+    let decoded = Proof::from_bincode(&bytes).unwrap();
+    assert_eq!(decoded.to_bincode().unwrap(), bytes);
+    assert_eq!(Proof::try_from(bytes.as_slice()).unwrap(), decoded);
+    assert_eq!(Proof::try_from(bytes.clone()).unwrap(), decoded);
+    assert_eq!(Vec::try_from(&decoded).unwrap(), bytes);
+    decoded
+        .verify(&ctx, case.vk, case.journal.digest())
+        .unwrap();
+
+    let mut trailing = bytes;
+    trailing.push(0);
+    assert!(matches!(
+        Proof::from_bincode(&trailing),
+        Err(risc0_verifier::SerError::Bincode(_))
+    ));
+    assert!(matches!(
+        Proof::try_from(trailing),
+        Err(risc0_verifier::SerError::Bincode(_))
+    ));
+}
+
+#[cfg(feature = "bincode")]
+#[rstest_reuse::apply(all)]
+fn content_digest_hashes_the_bincode_encoding<SC: CircuitCoreDef, RC: CircuitCoreDef>(
+    #[case] _ctx: VerifierContext<SC, RC>,
+    #[case] path: &str,
+) {
+    use risc0_verifier::sha::{Impl, Sha256 as _};
+    let case: Case = read_all(path).unwrap();
+    let proof = case.get_proof().unwrap();
+    let bytes = proof.to_bincode().unwrap();
+
+    let digest = proof.content_digest().unwrap();
+    assert_eq!(digest, *Impl::hash_bytes(&bytes));
+    assert_eq!(
+        Proof::from_bincode(&bytes)
+            .unwrap()
+            .content_digest()
+            .unwrap(),
+        digest
+    );
+
+    let mut other = proof.clone();
+    match other.inner.mut_composite() {
+        Ok(composite) => composite.segments[0].seal[0] ^= 1,
+        Err(_) => other.inner.mut_succinct().unwrap().seal[0] ^= 1,
+    }
+    assert_ne!(other.content_digest().unwrap(), digest);
+}
+
+// The content digests are used as storage keys: this value should change if and only if the
+// proof encoding is changed on purpose.
+#[cfg(feature = "bincode")]
+#[test]
+fn content_digest_is_stable() {
+    let case: Case = read_all("./resources/cases/prover_1.2.0/vm_1.2.0/succinct_16.json").unwrap();
+
+    assert_eq!(
+        case.get_proof()
+            .unwrap()
+            .content_digest()
+            .unwrap()
+            .to_string(),
+        "8d8683f724a21ad35fb8cb4279f5b7755643bbda8f00994a1f498cb0c2e6c1bd"
+    );
+}
+
+#[cfg(feature = "protobuf")]
+#[rstest_reuse::apply(all)]
+fn protobuf_round_trip<SC: CircuitCoreDef, RC: CircuitCoreDef>(
+    #[case] ctx: VerifierContext<SC, RC>,
+    #[case] path: &str,
+) {
+    let case: Case = read_all(path).unwrap();
+    let proof = case.get_proof().unwrap();
+
+    let bytes = proof.to_proto_bytes();
+    let decoded = Proof::from_proto_bytes(&bytes).unwrap();
+
+    assert_eq!(decoded, proof);
+    assert_eq!(decoded.to_proto_bytes(), bytes);
+    decoded
+        .verify(&ctx, case.vk, case.journal.digest())
+        .unwrap();
+    assert!(matches!(
+        Proof::from_proto_bytes(&bytes[..bytes.len() - 1]),
+        Err(risc0_verifier::SerError::Protobuf(_))
+    ));
+}
+
+#[cfg(feature = "protobuf")]
+#[test]
+fn protobuf_keeps_the_assumption_receipts() {
+    let succinct: risc0_verifier::InnerAssumptionReceipt =
+        read_all::<Case>("./resources/cases/prover_1.2.0/vm_1.2.0/succinct_16.json")
+            .unwrap()
+            .get_proof()
+            .unwrap()
+            .inner
+            .into();
+    let mut proof = read_all::<Case>("./resources/cases/prover_1.2.0/vm_1.2.0/sha_16.json")
+        .unwrap()
+        .get_proof()
+        .unwrap();
+    let composite = proof.inner.mut_composite().unwrap();
+    composite.assumption_receipts = vec![
+        succinct,
+        risc0_verifier::InnerAssumptionReceipt::Composite(composite.clone()),
+    ];
+
+    assert_eq!(
+        Proof::from_proto_bytes(&proof.to_proto_bytes()).unwrap(),
+        proof
+    );
+}
+
+#[cfg(feature = "serde-hex")]
+#[rstest_reuse::apply(all)]
+fn serde_hex_encodes_the_digests_and_the_seals_as_hex<SC: CircuitCoreDef, RC: CircuitCoreDef>(
+    #[case] ctx: VerifierContext<SC, RC>,
+    #[case] path: &str,
+) {
+    // The cases are stored with the digests and the seals as arrays of words.
+    let case: Case = read_all(path).unwrap();
+    let proof = case.get_proof().unwrap();
+
+    let json = serde_json::to_value(&proof).unwrap();
+    let (kind, receipt) = json["inner"].as_object().unwrap().iter().next().unwrap();
+    assert_eq!(
+        receipt["verifier_parameters"],
+        hex::encode(proof.verifier_parameters())
+    );
+    let sealed = match kind.as_str() {
+        "Composite" => &receipt["segments"][0],
+        _ => receipt,
+    };
+    assert!(sealed["seal"].is_string());
 
-        fn hash_ext_elem_slice(&self, _slice: &[<BabyBear as Field>::ExtElem]) -> Box<Digest> {
-            (Digest::ZERO).into()
-        }
-    }
+    let decoded: Proof = serde_json::from_value(json).unwrap();
+    assert_eq!(decoded, proof);
+    decoded
+        .verify(&ctx, case.vk, case.journal.digest())
+        .unwrap();
 
-    #[test]
-    fn should_work() {
-        let mut ctx = VerifierContext::v1_2();
-        let mut suites = ctx.suites.clone();
-        let mut sha = suites.get("sha-256").cloned().unwrap();
-        sha.hashfn = std::rc::Rc::new(CorrectSha256);
+    let bytes = bincode::serialize(&proof).unwrap();
+    assert_eq!(bincode::deserialize::<Proof>(&bytes).unwrap(), proof);
+}
 
-        suites.insert("sha-256".to_owned(), sha).unwrap();
-        ctx = ctx.with_suites(suites);
+#[cfg(feature = "json")]
+#[test]
+fn verify_a_risc0_json_receipt() {
+    // A risc0 1.2.0 succinct receipt exported with `serde_json`, journal and metadata included.
+    let json = std::fs::read_to_string("./resources/json/succinct_16.json").unwrap();
+    let case: Case = read_all("./resources/cases/prover_1.2.0/vm_1.2.0/succinct_16.json").unwrap();
+
+    let proof = Proof::from_risc0_json(&json).unwrap();
+
+    assert_eq!(proof, case.get_proof().unwrap());
+    proof
+        .verify(&VerifierContext::v1_2(), case.vk, case.journal.digest())
+        .unwrap();
+    assert_eq!(
+        Proof::from_risc0_json(&json.replace("\"Succinct\"", "\"Unknown\"")),
+        Err(VerificationError::ReceiptFormatError)
+    );
+}
 
-        let case: Case = read_all("./resources/cases/prover_1.2.0/vm_1.2.0/sha_16.json").unwrap();
+#[cfg(feature = "bincode")]
+#[rstest_reuse::apply(all)]
+fn read_the_proof_within_the_limits<SC: CircuitCoreDef, RC: CircuitCoreDef>(
+    #[case] ctx: VerifierContext<SC, RC>,
+    #[case] path: &str,
+) {
+    use risc0_verifier::{Limits, SerError};
 
-        let proof = case.get_proof().unwrap();
+    let case: Case = read_all(path).unwrap();
+    let proof = case.get_proof().unwrap();
+    let bytes = proof.to_bincode().unwrap();
+    let segments = proof.inner.composite().map_or(0, |c| c.segments.len());
 
-        proof.verify(&ctx, case.vk, case.journal.digest()).unwrap()
+    let read =
+        Proof::from_reader(bytes.as_slice(), &Limits::new(segments, bytes.len() as u64)).unwrap();
+    assert_eq!(read, proof);
+    read.verify(&ctx, case.vk.0, case.journal.digest()).unwrap();
+
+    assert_eq!(
+        Proof::from_reader(bytes.as_slice(), &Limits::new(segments, 1024)),
+        Err(SerError::TooLarge { max: 1024 })
+    );
+    if proof.is_composite() {
+        assert_eq!(
+            Proof::from_reader(bytes.as_slice(), &Limits::new(segments - 1, u64::MAX)),
+            Err(SerError::TooManySegments {
+                count: segments as u64,
+                max: segments - 1
+            })
+        );
     }
+    assert!(matches!(
+        Proof::from_reader(&bytes[..3], &Limits::new(segments, u64::MAX)),
+        Err(SerError::Bincode(_))
+    ));
+}
 
-    #[test]
-    fn should_fail() {
-        let mut ctx = VerifierContext::v1_2();
-        let mut suites = ctx.suites.clone();
-        let mut sha = suites.get("sha-256").cloned().unwrap();
-        sha.hashfn = std::rc::Rc::new(FakeSha256);
+#[cfg(feature = "zeroize")]
+#[rstest_reuse::apply(all)]
+fn zeroize_the_seals<SC: CircuitCoreDef, RC: CircuitCoreDef>(
+    #[case] _ctx: VerifierContext<SC, RC>,
+    #[case] path: &str,
+) {
+    use risc0_verifier::SegmentReceipt;
+    use zeroize::{Zeroize, ZeroizeOnDrop};
+
+    fn is_zeroized_on_drop<T: ZeroizeOnDrop>(_: &T) {}
+
+    // The zeroized seal keeps its buffer: peek it to check that it's scrubbed.
+    fn assert_scrubbed<T: Zeroize>(receipt: &mut T, seal: fn(&T) -> &Vec<u32>) {
+        let len = seal(receipt).len();
+        receipt.zeroize();
+        let buffer = unsafe { std::slice::from_raw_parts(seal(receipt).as_ptr(), len) };
+        assert!(buffer.iter().all(|&word| word == 0));
+        assert!(seal(receipt).is_empty());
+    }
 
-        suites.insert("sha-256".to_owned(), sha).unwrap();
-        ctx = ctx.with_suites(suites);
+    let case: Case = read_all(path).unwrap();
+    match case.get_proof().unwrap().inner {
+        InnerReceipt::Composite(composite) => {
+            let mut segment: SegmentReceipt = composite.segments[0].clone();
+            is_zeroized_on_drop(&segment);
+            assert!(segment.seal.iter().any(|&word| word != 0));
+            assert_scrubbed(&mut segment, |segment| &segment.seal);
+        }
+        InnerReceipt::Succinct(mut succinct) => {
+            is_zeroized_on_drop(&succinct);
+            assert_scrubbed(&mut succinct, |succinct| &succinct.seal);
+        }
+        #[allow(unreachable_patterns)]
+        _ => unreachable!(),
+    }
+}
 
-        let case: Case = read_all("./resources/cases/prover_1.2.0/vm_1.2.0/sha_16.json").unwrap();
+#[rstest_reuse::apply(all)]
+fn validate_the_proof_structure<SC: CircuitCoreDef, RC: CircuitCoreDef>(
+    #[case] _ctx: VerifierContext<SC, RC>,
+    #[case] path: &str,
+) {
+    let case: Case = read_all(path).unwrap();
+    let proof = case.get_proof().unwrap();
+    proof.validate_structure().unwrap();
+
+    let mut truncated = proof.clone();
+    match &mut truncated.inner {
+        InnerReceipt::Composite(composite) => composite.segments[0].seal.truncate(100),
+        InnerReceipt::Succinct(succinct) => succinct.seal.truncate(32),
+        #[allow(unreachable_patterns)]
+        _ => unreachable!(),
+    }
+    assert_eq!(
+        truncated.validate_structure(),
+        Err(VerificationError::ReceiptFormatError)
+    );
 
-        let proof = case.get_proof().unwrap();
+    if proof.is_composite() {
+        let mut empty = proof.clone();
+        empty.inner.mut_composite().unwrap().segments.clear();
+        assert_eq!(
+            empty.validate_structure(),
+            Err(VerificationError::ReceiptFormatError)
+        );
 
-        proof
-            .verify(&ctx, case.vk, case.journal.digest())
-            .unwrap_err();
+        let mut bad_po2 = proof;
+        bad_po2.inner.mut_composite().unwrap().segments[0].seal[SC::OUTPUT_SIZE] = 30;
+        assert_eq!(
+            bad_po2.validate_structure(),
+            Err(VerificationError::ReceiptFormatError)
+        );
     }
+}
 
-    pub struct LocPoseidon2;
-
-    impl Poseidon2Mix for LocPoseidon2 {
-        #[inline]
-        fn poseidon2_mix(cells: &mut [BabyBearElem; POSEIDON2_CELLS]) {
-            poseidon2_mix(cells);
-        }
+#[rstest_reuse::apply(segments)]
+fn composite_can_be_lifted_with_the_allowed_control_ids<SC: CircuitCoreDef, RC: CircuitCoreDef>(
+    #[case] ctx: VerifierContext<SC, RC>,
+    #[case] path: &str,
+) {
+    let case: Case = read_all(path).unwrap();
+    let proof = case.get_proof().unwrap();
+    let composite = proof.inner.composite().unwrap();
+
+    assert!(proof.needs_compression());
+    composite.can_lift(&ctx).unwrap();
+    let allowed = &ctx
+        .segment_verifier_parameters
+        .as_ref()
+        .unwrap()
+        .control_ids;
+    for segment in &composite.segments {
+        assert!(allowed.contains(&segment.control_id(&ctx).unwrap()));
     }
 
-    #[test]
-    fn should_poseidon2_work() {
-        let ctx = VerifierContext::v1_2().with_poseidon2_mix(LocPoseidon2);
+    let mut params = ctx.segment_verifier_parameters.clone().unwrap();
+    params.control_ids.clear();
+    let ctx = ctx.with_segment_verifier_parameters(params);
+    assert!(matches!(
+        composite.can_lift(&ctx),
+        Err(VerificationError::ControlVerificationError { .. })
+    ));
+}
 
-        let case: Case =
-            read_all("./resources/cases/prover_1.2.0/vm_1.2.0/poseidon2_22.json").unwrap();
+#[rstest_reuse::apply(segments)]
+fn segments_verify_only_with_the_allowed_control_ids<SC: CircuitCoreDef, RC: CircuitCoreDef>(
+    #[case] ctx: VerifierContext<SC, RC>,
+    #[case] path: &str,
+) {
+    let case: Case = read_all(path).unwrap();
+    let proof = case.get_proof().unwrap();
+    let composite = proof.inner.composite().unwrap();
+    let ids = composite
+        .segments
+        .iter()
+        .map(|segment| segment.control_id(&ctx).unwrap())
+        .collect::<Vec<_>>();
+
+    let ctx = ctx.with_allowed_control_ids(ids);
+    proof
+        .verify(&ctx, case.vk.0, case.journal.digest())
+        .unwrap();
+
+    let ctx = ctx.with_allowed_control_ids([Digest::ZERO]);
+    assert!(matches!(
+        proof.verify(&ctx, case.vk.0, case.journal.digest()),
+        Err(VerificationError::ControlVerificationError { .. })
+    ));
+}
 
-        let proof = case.get_proof().unwrap();
+#[rstest_reuse::apply(succinct)]
+fn succinct_verifies_only_with_the_allowed_control_ids<SC: CircuitCoreDef, RC: CircuitCoreDef>(
+    #[case] ctx: VerifierContext<SC, RC>,
+    #[case] path: &str,
+) {
+    use risc0_verifier::circuit::{v1_0, v1_1, v1_2};
+    let case: Case = read_all(path).unwrap();
+    let proof = case.get_proof().unwrap();
+    let recursion_ids = [
+        v1_0::recursive::control_id::ALLOWED_CONTROL_IDS,
+        v1_1::recursive::control_id::ALLOWED_CONTROL_IDS,
+        v1_2::recursive::control_id::ALLOWED_CONTROL_IDS,
+    ];
+
+    let ctx = ctx.with_allowed_control_ids(recursion_ids.concat());
+    proof
+        .verify(&ctx, case.vk.0, case.journal.digest())
+        .unwrap();
+
+    let ctx = ctx.with_allowed_control_ids([]);
+    assert!(matches!(
+        proof.verify(&ctx, case.vk.0, case.journal.digest()),
+        Err(VerificationError::ControlVerificationError { .. })
+    ));
+}
 
-        proof.verify(&ctx, case.vk, case.journal.digest()).unwrap()
-    }
+#[rstest_reuse::apply(segments)]
+fn sum_the_segment_cycles<SC: CircuitCoreDef, RC: CircuitCoreDef>(
+    #[case] _ctx: VerifierContext<SC, RC>,
+    #[case] path: &str,
+) {
+    let case: Case = read_all(path).unwrap();
+    let proof = case.get_proof().unwrap();
+    let composite = proof.inner.composite().unwrap();
+    let expected: u64 = composite
+        .segments
+        .iter()
+        .map(|segment| 1 << segment.po2::<SC>().unwrap())
+        .sum();
+
+    assert_eq!(composite.total_cycles::<SC>().unwrap(), expected);
+
+    let mut invalid = composite.clone();
+    invalid.segments[0].seal[SC::OUTPUT_SIZE] = 30;
+    assert_eq!(
+        invalid.total_cycles::<SC>(),
+        Err(VerificationError::ReceiptFormatError)
+    );
 }
 
 #[rstest_reuse::apply(segments)]
-fn fails_on_invalid_segment<SC: CircuitCoreDef, RC: CircuitCoreDef>(
+fn report_the_segment_verification_progress<SC: CircuitCoreDef, RC: CircuitCoreDef>(
     #[case] ctx: VerifierContext<SC, RC>,
     #[case] path: &str,
-    #[values(0, 1, 2)] segment: usize,
 ) {
     let case: Case = read_all(path).unwrap();
-    let mut proof = case.get_proof().unwrap();
+    let proof = case.get_proof().unwrap();
+    let composite = proof.inner.composite().unwrap();
+    let total = composite.segments.len();
+    let mut calls = Vec::new();
+
+    composite
+        .verify_integrity_with_progress(&ctx, &mut |completed, total| {
+            calls.push((completed, total))
+        })
+        .unwrap();
+
+    assert_eq!(calls, (1..=total).map(|i| (i, total)).collect::<Vec<_>>());
+
+    let mut broken = composite.clone();
+    broken.segments[total - 1].seal[0] ^= 1;
+    calls.clear();
+    assert!(broken
+        .verify_integrity_with_progress(&ctx, &mut |completed, total| {
+            calls.push((completed, total))
+        })
+        .is_err());
+    assert_eq!(calls.len(), total - 1);
+}
 
-    let seal = proof.inner.mut_composite().unwrap().segments[segment]
-        .seal
-        .as_mut_slice();
+#[rstest_reuse::apply(all)]
+fn peek_the_receipt_kind_of_the_encoded_proof<SC: CircuitCoreDef, RC: CircuitCoreDef>(
+    #[case] _ctx: VerifierContext<SC, RC>,
+    #[case] path: &str,
+) {
+    let case: Case = read_all(path).unwrap();
+    let proof = case.get_proof().unwrap();
+    let bytes = bincode::serialize(&proof).unwrap();
 
-    seal[seal.len() / 2] = seal[seal.len() / 2].wrapping_add(1);
+    assert_eq!(Proof::peek_kind(&bytes), Ok(proof.kind()));
+    assert_eq!(Proof::peek_kind(&bytes[..4]), Ok(proof.kind()));
+    assert_eq!(
+        Proof::peek_kind(&bytes[..3]),
+        Err(VerificationError::ReceiptFormatError)
+    );
+    assert_eq!(
+        Proof::peek_kind(&[4, 0, 0, 0]),
+        Err(VerificationError::ReceiptFormatError)
+    );
+}
 
-    let res = proof.verify(&ctx, case.vk, case.journal.digest());
+#[test]
+fn peek_the_tags_enabled_by_the_features() {
+    let peek = |tag: u8| Proof::peek_kind(&[tag, 0, 0, 0]).ok();
+
+    assert_eq!(peek(0), Some(ReceiptKind::Composite));
+    assert_eq!(peek(1), Some(ReceiptKind::Succinct));
+    #[cfg(all(not(feature = "groth16"), not(feature = "unsafe-fake")))]
+    assert_eq!(peek(2), None);
+    #[cfg(all(feature = "groth16", not(feature = "unsafe-fake")))]
+    assert_eq!((peek(2), peek(3)), (Some(ReceiptKind::Groth16), None));
+    #[cfg(all(not(feature = "groth16"), feature = "unsafe-fake"))]
+    assert_eq!((peek(2), peek(3)), (Some(ReceiptKind::Fake), None));
+    #[cfg(all(feature = "groth16", feature = "unsafe-fake"))]
+    assert_eq!(
+        (peek(2), peek(3), peek(4)),
+        (Some(ReceiptKind::Groth16), Some(ReceiptKind::Fake), None)
+    );
+}
 
-    assert!(res.is_err());
-    assert!(matches!(res, Err(VerificationError::InvalidProof { .. })));
+#[cfg(feature = "unsafe-fake")]
+#[test]
+fn peek_the_kind_of_an_encoded_fake_receipt() {
+    let claim = ReceiptClaim::ok(Digest::ZERO, vec![]);
+    let proof = Proof::new(InnerReceipt::Fake(risc0_verifier::FakeReceipt::new(claim)));
+
+    assert_eq!(
+        Proof::peek_kind(&bincode::serialize(&proof).unwrap()),
+        Ok(ReceiptKind::Fake)
+    );
 }
 
+#[cfg(feature = "bincode")]
 #[rstest_reuse::apply(succinct)]
-fn fails_on_invalid_succinct<SC: CircuitCoreDef, RC: CircuitCoreDef>(
+fn succinct_bytes_round_trip<SC: CircuitCoreDef, RC: CircuitCoreDef>(
     #[case] ctx: VerifierContext<SC, RC>,
     #[case] path: &str,
 ) {
     let case: Case = read_all(path).unwrap();
-    let mut proof = case.get_proof().unwrap();
+    let proof = case.get_proof().unwrap();
+    let succinct = proof.inner.succinct().unwrap();
 
-    let seal = proof.inner.mut_succinct().unwrap().seal.as_mut_slice();
+    let bytes = succinct.to_bytes().unwrap();
+    assert!(bytes.len() < proof.to_bincode().unwrap().len());
 
-    seal[seal.len() / 2] = seal[seal.len() / 2].wrapping_add(1);
+    let decoded = SuccinctReceipt::<ReceiptClaim>::from_bytes(&bytes).unwrap();
+    assert_eq!(decoded.to_bytes().unwrap(), bytes);
+    decoded.verify_integrity_with_context(&ctx).unwrap();
+    assert_eq!(decoded.claim.digest(), succinct.claim.digest());
+}
 
-    let res = proof.verify(&ctx, case.vk, case.journal.digest());
+#[cfg(feature = "borsh")]
+#[rstest_reuse::apply(all)]
+fn borsh_round_trip<SC: CircuitCoreDef, RC: CircuitCoreDef>(
+    #[case] ctx: VerifierContext<SC, RC>,
+    #[case] path: &str,
+) {
+    let case: Case = read_all(path).unwrap();
+    let proof = case.get_proof().unwrap();
 
-    assert!(res.is_err());
-    assert!(matches!(res, Err(VerificationError::InvalidProof { .. })));
+    let bytes = borsh::to_vec(&proof).unwrap();
+    let decoded: Proof = borsh::from_slice(&bytes).unwrap();
+    assert_eq!(borsh::to_vec(&decoded).unwrap(), bytes);
+
+    let journal: Journal = borsh::from_slice(&borsh::to_vec(&case.journal).unwrap()).unwrap();
+    assert_eq!(journal, case.journal);
+
+    decoded.verify(&ctx, case.vk, journal.digest()).unwrap();
+}
+
+#[rstest_reuse::apply(all)]
+fn verify_with_exit_accepts_only_the_given_exit_codes<SC: CircuitCoreDef, RC: CircuitCoreDef>(
+    #[case] ctx: VerifierContext<SC, RC>,
+    #[case] path: &str,
+) {
+    use risc0_verifier::ExitCode;
+
+    let case: Case = read_all(path).unwrap();
+    let proof = case.get_proof().unwrap();
+    let pubs = case.journal.digest();
+
+    assert_eq!(
+        proof.verify_with_exit(
+            &ctx,
+            case.vk.0,
+            pubs,
+            &[ExitCode::Paused(0), ExitCode::Halted(0)]
+        ),
+        Ok(ExitCode::Halted(0))
+    );
+    assert_eq!(
+        proof.verify_with_exit(&ctx, case.vk.0, pubs, &[ExitCode::Paused(0)]),
+        Err(VerificationError::UnexpectedExitCode)
+    );
+    assert!(matches!(
+        proof.verify_with_exit(
+            &ctx,
+            risc0_verifier::Digest::ZERO,
+            pubs,
+            &[ExitCode::Halted(0)]
+        ),
+        Err(VerificationError::ClaimDigestMismatch { .. })
+    ));
 }
 
 #[rstest_reuse::apply(all)]
@@ -298,11 +2173,9 @@ fn fails_on_invalid_vk<SC: CircuitCoreDef, RC: CircuitCoreDef>(
     let mut case: Case = read_all(path).unwrap();
     let proof = case.get_proof().unwrap();
 
-    case.vk
-        .0
-        .as_mut_words()
-        .last_mut()
-        .map(|l| *l = l.wrapping_add(1));
+    if let Some(l) = case.vk.0.as_mut_words().last_mut() {
+        *l = l.wrapping_add(1);
+    }
 
     let res = proof.verify(&ctx, case.vk, case.journal.digest());
 
@@ -321,10 +2194,9 @@ fn fails_on_invalid_pubs<SC: CircuitCoreDef, RC: CircuitCoreDef>(
     let mut case: Case = read_all(path).unwrap();
     let proof = case.get_proof().unwrap();
 
-    case.journal
-        .bytes
-        .last_mut()
-        .map(|l| *l = l.wrapping_add(1));
+    if let Some(l) = case.journal.bytes.last_mut() {
+        *l = l.wrapping_add(1);
+    }
 
     let res = proof.verify(&ctx, case.vk, case.journal.digest());
 
@@ -364,9 +2236,9 @@ fn fails_on_invalid_inner_control_root<SC: CircuitCoreDef, RC: CircuitCoreDef>(
     let case: Case = read_all(path).unwrap();
     let proof = case.get_proof().unwrap();
 
-    ctx.succinct_verifier_parameters
-        .as_mut()
-        .map(|p| p.inner_control_root = Some(risc0_verifier::Digest::ZERO));
+    if let Some(p) = ctx.succinct_verifier_parameters.as_mut() {
+        p.inner_control_root = Some(risc0_verifier::Digest::ZERO);
+    }
 
     let res = proof.verify(&ctx, case.vk, case.journal.digest());
 
@@ -391,7 +2263,79 @@ fn fails_on_invalid_succinct_claim<SC: CircuitCoreDef, RC: CircuitCoreDef>(
 
     assert!(res.is_err());
     assert!(
-        matches!(res, Err(VerificationError::JournalDigestMismatch { .. })),
+        matches!(res, Err(VerificationError::JournalDigestMismatch)),
+        "Invalid err {res:?}"
+    );
+}
+
+#[rstest_reuse::apply(all)]
+fn verify_and_extract_returns_the_claim<SC: CircuitCoreDef, RC: CircuitCoreDef>(
+    #[case] ctx: VerifierContext<SC, RC>,
+    #[case] path: &str,
+) {
+    let case: Case = read_all(path).unwrap();
+    let proof = case.get_proof().unwrap();
+
+    let claim = proof
+        .verify_and_extract(&ctx, case.vk, case.journal.digest())
+        .unwrap();
+
+    assert_eq!(claim.exit_code, risc0_binfmt::ExitCode::Halted(0));
+    assert_eq!(claim.digest(), proof.claim().unwrap().digest());
+}
+
+#[rstest_reuse::apply(succinct)]
+fn verify_and_extract_fails_on_pruned_claim<SC: CircuitCoreDef, RC: CircuitCoreDef>(
+    #[case] ctx: VerifierContext<SC, RC>,
+    #[case] path: &str,
+) {
+    let case: Case = read_all(path).unwrap();
+    let proof = case.get_proof().unwrap();
+    let digest = proof.claim().unwrap().digest();
+
+    // Build a receipt with the same claim digest but pruned.
+    let mut pruned = proof.clone();
+    pruned.inner.mut_succinct().unwrap().claim = MaybePruned::Pruned(digest);
+
+    let res = pruned.verify_and_extract(&ctx, case.vk, case.journal.digest());
+
+    assert!(
+        matches!(res, Err(risc0_verifier::Error::PrunedClaim(d)) if d == digest),
+        "Invalid err {res:?}"
+    );
+}
+
+#[rstest_reuse::apply(all)]
+fn verify_any_returns_the_matching_image_id<SC: CircuitCoreDef, RC: CircuitCoreDef>(
+    #[case] ctx: VerifierContext<SC, RC>,
+    #[case] path: &str,
+) {
+    let case: Case = read_all(path).unwrap();
+    let proof = case.get_proof().unwrap();
+    let image_id: risc0_verifier::Digest = case.vk.into();
+    let other = risc0_verifier::Digest::ZERO;
+
+    assert_eq!(
+        proof
+            .verify_any(&ctx, &[other, image_id, other], case.journal.digest())
+            .unwrap(),
+        image_id
+    );
+
+    let res = proof.verify_any(&ctx, &[other], case.journal.digest());
+    assert!(
+        matches!(
+            res,
+            Err(risc0_verifier::Error::Verification(
+                VerificationError::ImageVerificationError
+            ))
+        ),
+        "Invalid err {res:?}"
+    );
+
+    let res = proof.verify_any(&ctx, &[], case.journal.digest());
+    assert!(
+        matches!(res, Err(risc0_verifier::Error::EmptyImageIds)),
         "Invalid err {res:?}"
     );
 }
@@ -536,3 +2480,200 @@ impl ExInnerReceipt for risc0_verifier::InnerReceipt {
         }
     }
 }
+
+#[rstest_reuse::apply(all)]
+fn header_describes_the_proof_without_the_seals<SC: CircuitCoreDef, RC: CircuitCoreDef>(
+    #[case] _ctx: VerifierContext<SC, RC>,
+    #[case] path: &str,
+) {
+    let case: Case = read_all(path).unwrap();
+    let proof = case.get_proof().unwrap();
+    let header = proof.header();
+
+    assert_eq!(header.kind, proof.kind());
+    assert_eq!(header.verifier_parameters, proof.verifier_parameters());
+    assert_eq!(
+        header.claim_digest,
+        Some(proof.inner.claim().unwrap().digest())
+    );
+    match &proof.inner {
+        InnerReceipt::Composite(inner) => {
+            assert_eq!(header.segment_count, inner.segment_count());
+            assert_eq!(
+                header.total_cycles,
+                Some(inner.total_cycles::<SC>().unwrap())
+            );
+        }
+        _ => {
+            assert_eq!(header.segment_count, 0);
+            assert_eq!(header.total_cycles, None);
+        }
+    }
+
+    let json = serde_json::to_value(header).unwrap();
+    assert!(json.get("seal").is_none());
+    assert_eq!(json["kind"], proof.kind().name());
+}
+
+#[rstest_reuse::apply(segments)]
+fn composite_limits_bound_the_segments_and_the_assumptions<
+    SC: CircuitCoreDef,
+    RC: CircuitCoreDef,
+>(
+    #[case] ctx: VerifierContext<SC, RC>,
+    #[case] path: &str,
+) {
+    use risc0_verifier::{Error, LimitKind};
+
+    let case: Case = read_all(path).unwrap();
+    let mut proof = case.get_proof().unwrap();
+    let segments = proof.inner.composite().unwrap().segment_count();
+    let ctx = ctx.with_max_segments(segments).with_max_assumptions(0);
+    proof.check_limits(&ctx).unwrap();
+    proof
+        .verify(&ctx, case.vk.0, case.journal.digest())
+        .unwrap();
+
+    let ctx = ctx.with_max_segments(segments - 1);
+    assert_eq!(
+        proof.check_limits(&ctx),
+        Err(Error::LimitExceeded {
+            kind: LimitKind::Segments,
+            limit: segments - 1,
+            found: segments
+        })
+    );
+    assert_eq!(
+        proof.verify(&ctx, case.vk.0, case.journal.digest()),
+        Err(VerificationError::ReceiptFormatError)
+    );
+
+    let ctx = ctx.with_max_segments(segments);
+    let composite = proof.inner.mut_composite().unwrap();
+    composite
+        .assumption_receipts
+        .push(risc0_verifier::InnerAssumptionReceipt::Composite(
+            composite.clone(),
+        ));
+    assert_eq!(
+        proof.check_limits(&ctx),
+        Err(Error::LimitExceeded {
+            kind: LimitKind::Assumptions,
+            limit: 0,
+            found: 1
+        })
+    );
+}
+
+#[cfg(feature = "bincode")]
+#[rstest_reuse::apply(all)]
+fn versioned_bytes_tag_the_format<SC: CircuitCoreDef, RC: CircuitCoreDef>(
+    #[case] _ctx: VerifierContext<SC, RC>,
+    #[case] path: &str,
+) {
+    use risc0_verifier::{SerError, PROOF_FORMAT_MAGIC, PROOF_FORMAT_VERSION};
+
+    let case: Case = read_all(path).unwrap();
+    let proof = case.get_proof().unwrap();
+    let bytes = proof.to_bytes().unwrap();
+
+    assert_eq!(bytes[..4], PROOF_FORMAT_MAGIC);
+    assert_eq!(bytes[4..6], PROOF_FORMAT_VERSION.to_le_bytes());
+    assert_eq!(bytes[6..], proof.to_bincode().unwrap());
+    assert_eq!(Proof::from_bytes(&bytes).unwrap(), proof);
+    assert_eq!(Proof::peek_kind(&bytes), Ok(proof.kind()));
+    assert_eq!(
+        Proof::peek_kind(&bytes[..6]),
+        Err(VerificationError::ReceiptFormatError)
+    );
+
+    assert_eq!(
+        Proof::from_bytes(&proof.to_bincode().unwrap()),
+        Err(SerError::MissingFormatHeader)
+    );
+    assert_eq!(
+        Proof::from_bytes(&bytes[..5]),
+        Err(SerError::MissingFormatHeader)
+    );
+    let mut newer = bytes;
+    newer[4..6].copy_from_slice(&(PROOF_FORMAT_VERSION + 1).to_le_bytes());
+    assert_eq!(
+        Proof::from_bytes(&newer),
+        Err(SerError::UnsupportedProofFormatVersion {
+            found: PROOF_FORMAT_VERSION + 1,
+            supported: PROOF_FORMAT_VERSION
+        })
+    );
+    assert_eq!(
+        Proof::peek_kind(&newer),
+        Err(VerificationError::ReceiptFormatError)
+    );
+}
+
+#[test]
+fn succinct_proves_the_same_claim_as_the_composite() {
+    let read = |path| read_all::<Case>(path).unwrap().get_proof().unwrap();
+    let mut composite = read("./resources/cases/prover_1.2.0/vm_1.2.0/sha_22.json");
+    let succinct = read("./resources/cases/prover_1.2.0/vm_1.2.0/succinct_22.json");
+    let composite = composite.inner.mut_composite().unwrap();
+    let succinct = succinct.inner.succinct().unwrap();
+
+    succinct.proves_same_claim_as(composite).unwrap();
+    succinct
+        .clone()
+        .into_unknown()
+        .proves_same_claim_as(composite)
+        .unwrap();
+
+    let other = read("./resources/cases/prover_1.2.0/vm_1.2.0/sha_16.json");
+    assert!(matches!(
+        succinct.proves_same_claim_as(other.inner.composite().unwrap()),
+        Err(VerificationError::ClaimDigestMismatch { .. })
+    ));
+
+    let output = &mut composite.segments.last_mut().unwrap().claim.output;
+    *output = MaybePruned::Pruned(output.digest());
+    assert_eq!(
+        succinct.proves_same_claim_as(composite),
+        Err(VerificationError::ReceiptFormatError)
+    );
+}
+
+#[test]
+fn assumption_digests_list_the_assumed_claims() {
+    use risc0_verifier::{Assumption, Assumptions, Digest, Output};
+
+    let mut proof = read_all::<Case>("./resources/cases/prover_1.2.0/vm_1.2.0/sha_16.json")
+        .unwrap()
+        .get_proof()
+        .unwrap();
+    let composite = proof.inner.mut_composite().unwrap();
+    assert_eq!(composite.assumption_digests(), Ok(vec![]));
+
+    let claims = [Digest::from([1; 8]), Digest::from([2; 8])];
+    let assumptions = Assumptions(
+        claims
+            .iter()
+            .map(|&claim| {
+                MaybePruned::Value(Assumption {
+                    claim,
+                    control_root: Digest::ZERO,
+                })
+            })
+            .collect(),
+    );
+    let cases = [
+        (
+            MaybePruned::Pruned(assumptions.digest()),
+            Err(VerificationError::ReceiptFormatError),
+        ),
+        (MaybePruned::Value(assumptions), Ok(claims.to_vec())),
+    ];
+    for (assumptions, expected) in cases {
+        composite.segments.last_mut().unwrap().claim.output = MaybePruned::Value(Some(Output {
+            journal: MaybePruned::Value(vec![]),
+            assumptions,
+        }));
+        assert_eq!(composite.assumption_digests(), expected);
+    }
+}